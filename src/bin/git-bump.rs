@@ -1,15 +1,18 @@
 //! Bumps the version.
 
-use std::process::exit;
+use std::{collections::BTreeMap, process::exit};
 
-use clap::Parser;
+use clap::{ArgEnum, Parser};
 
 use console::{style, Term};
 use dialoguer::{theme::ColorfulTheme, Confirm};
 use gitext::{
-    commands::shared::{load_config, set_current_dir_from_arg},
+    commands::shared::{load_config, set_current_dir_from_arg, EXIT_NO_RELEASE},
     git::{git_set_tag, git_status_porcelain},
-    version::get_repo_next_version,
+    version::{
+        explain_repo_next_version, get_release_channel, get_repo_next_version, tag::tag_pattern,
+        BumpOptions, VersionExplanation,
+    },
 };
 
 /// Bump command
@@ -25,6 +28,39 @@ pub struct Cli {
     /// If set, the repo is tagged with the new version
     #[clap(long)]
     pub tag: bool,
+    /// Pre-release channel (eg. `beta`), overriding the channel of the current branch
+    #[clap(long)]
+    pub pre: Option<String>,
+    /// Version of the 1st release, if the repo has no version tag (defaults to 0.0.1, or the CalVer version of today)
+    #[clap(long)]
+    pub initial_version: Option<String>,
+    /// Releases 1.0.0 from a pre-1.0 version
+    #[clap(long)]
+    pub graduate: bool,
+    /// Explains the next version, with the version increment of each commit (the repo is not tagged)
+    #[clap(
+        long,
+        arg_enum,
+        min_values = 0,
+        require_equals = true,
+        default_missing_value = "table"
+    )]
+    pub explain: Option<ExplainFormat>,
+    /// Package to bump (cf. `[packages.<name>]`), can be repeated
+    #[clap(long, multiple_occurrences = true)]
+    pub package: Vec<String>,
+    /// Bumps all the packages
+    #[clap(long, conflicts_with = "package")]
+    pub all_packages: bool,
+}
+
+/// Format of the explanation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub enum ExplainFormat {
+    /// Table (on stderr)
+    Table,
+    /// JSON (on stdout)
+    Json,
 }
 
 fn main() {
@@ -40,6 +76,114 @@ fn main() {
     // load the config
     let config = load_config(&cwd, true);
 
+    // get the repo or the packages to bump
+    let configs = match config.release_configs(&args.package, args.all_packages) {
+        Ok(configs) => configs,
+        Err(err) => {
+            term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
+                .unwrap();
+            exit(1);
+        }
+    };
+
+    // get the pre-release channel
+    let channel = match get_release_channel(&config, args.pre.as_deref()) {
+        Ok(channel) => channel,
+        Err(err) => {
+            term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
+                .unwrap();
+            exit(1);
+        }
+    };
+
+    let options = BumpOptions {
+        channel,
+        initial_version: args.initial_version.clone(),
+        graduate: args.graduate,
+    };
+
+    // explain the next version
+    if let Some(format) = args.explain {
+        let mut explanations = BTreeMap::<String, VersionExplanation>::new();
+        for config in &configs {
+            let package = config.current_package().map(|(name, _)| name);
+            let label = package.map(|name| format!("{name}: ")).unwrap_or_default();
+            let explanation = match explain_repo_next_version(config, &options) {
+                Ok(explanation) => explanation,
+                Err(err) if err.is_no_release() => {
+                    term.write_line(
+                        style(format!("i {label}{err}"))
+                            .yellow()
+                            .to_string()
+                            .as_str(),
+                    )
+                    .unwrap();
+                    continue;
+                }
+                Err(err) => {
+                    term.write_line(style(format!("✗ {label}{err}")).red().to_string().as_str())
+                        .unwrap();
+                    exit(1);
+                }
+            };
+            if format == ExplainFormat::Table {
+                if let Some(name) = package {
+                    term.write_line(style(format!("Package {name}")).bold().to_string().as_str())
+                        .unwrap();
+                }
+                term.write_line(&explanation.to_table()).unwrap();
+                term.write_line(
+                    format!(
+                        "{} {}",
+                        style("i").yellow(),
+                        style(format!(
+                            "{label}{} --> {}",
+                            explanation
+                                .current
+                                .as_ref()
+                                .map(|v| v.to_string())
+                                .unwrap_or_else(|| "<none>".to_string()),
+                            explanation
+                                .next
+                                .as_ref()
+                                .map(|v| v.to_string())
+                                .unwrap_or_else(|| "<none>".to_string())
+                        ))
+                        .bold(),
+                    )
+                    .as_str(),
+                )
+                .unwrap();
+            }
+            explanations.insert(package.unwrap_or_default().to_string(), explanation);
+        }
+
+        if format == ExplainFormat::Json {
+            // NB: the explanations of several packages are keyed by package name
+            let json = if configs.len() == 1 {
+                explanations
+                    .values()
+                    .next()
+                    .map(|e| e.to_json().map_err(|err| err.to_string()))
+            } else {
+                Some(serde_json::to_string_pretty(&explanations).map_err(|err| err.to_string()))
+            };
+            match json {
+                Some(Ok(json)) => println!("{json}"),
+                Some(Err(err)) => {
+                    term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
+                        .unwrap();
+                    exit(1);
+                }
+                None => {}
+            }
+        }
+        if explanations.values().all(|e| e.next.is_none()) {
+            exit(EXIT_NO_RELEASE);
+        }
+        exit(0);
+    }
+
     // check if the repo is pristine
     if !args.allow_dirty {
         let commit_status = match git_status_porcelain() {
@@ -80,32 +224,64 @@ fn main() {
     }
 
     // bump
-    let (next_version, curr_version) = match get_repo_next_version(&config) {
-        Ok(commits) => commits,
-        Err(err) => {
-            term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
+    let mut next_git_versions = Vec::<String>::new();
+    for config in &configs {
+        let label = config
+            .current_package()
+            .map(|(name, _)| format!("{name}: "))
+            .unwrap_or_default();
+
+        // get the tag pattern
+        let tag_pattern = match tag_pattern(config) {
+            Ok(pattern) => pattern,
+            Err(err) => {
+                term.write_line(style(format!("✗ {label}{err}")).red().to_string().as_str())
+                    .unwrap();
+                exit(1);
+            }
+        };
+
+        let (next_version, curr_version) = match get_repo_next_version(config, &options) {
+            Ok(commits) => commits,
+            Err(err) if err.is_no_release() => {
+                term.write_line(
+                    style(format!("i {label}{err}"))
+                        .yellow()
+                        .to_string()
+                        .as_str(),
+                )
                 .unwrap();
-            exit(1);
-        }
-    };
-    term.write_line(
-        format!(
-            "{} {}",
-            style("i").yellow(),
-            style(format!(
-                "{} --> {}",
-                curr_version
-                    .map(|v| v.to_string())
-                    .unwrap_or_else(|| "<none>".to_string()),
-                next_version
-            ))
-            .bold(),
+                continue;
+            }
+            Err(err) => {
+                term.write_line(style(format!("✗ {label}{err}")).red().to_string().as_str())
+                    .unwrap();
+                exit(1);
+            }
+        };
+        term.write_line(
+            format!(
+                "{} {}",
+                style("i").yellow(),
+                style(format!(
+                    "{label}{} --> {}",
+                    curr_version
+                        .map(|v| v.to_string())
+                        .unwrap_or_else(|| "<none>".to_string()),
+                    next_version
+                ))
+                .bold(),
+            )
+            .as_str(),
         )
-        .as_str(),
-    )
-    .unwrap();
+        .unwrap();
 
-    let next_git_version = format!("v{}", next_version);
+        next_git_versions.push(tag_pattern.format(&next_version));
+    }
+
+    if next_git_versions.is_empty() {
+        exit(EXIT_NO_RELEASE);
+    }
 
     // dry run
     if !args.tag {
@@ -118,32 +294,34 @@ fn main() {
             .as_str(),
         )
         .unwrap();
-        print!("{}", next_git_version);
+        print!("{}", next_git_versions.join("\n"));
         exit(0);
     }
 
-    // Tag the repo with the new version
-    match git_set_tag(
-        &next_git_version,
-        format!("Version {next_git_version}").as_str(),
-    ) {
-        Ok(_) => {
-            term.write_line(
-                format!(
-                    "{} {}",
-                    style("✔").green(),
-                    style(format!("Repo tagged as {next_git_version}")).bold()
+    // Tag the repo with the new versions
+    for next_git_version in &next_git_versions {
+        match git_set_tag(
+            next_git_version,
+            format!("Version {next_git_version}").as_str(),
+        ) {
+            Ok(_) => {
+                term.write_line(
+                    format!(
+                        "{} {}",
+                        style("✔").green(),
+                        style(format!("Repo tagged as {next_git_version}")).bold()
+                    )
+                    .as_str(),
                 )
-                .as_str(),
-            )
-            .unwrap();
-        }
-        Err(err) => {
-            term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
                 .unwrap();
-            exit(1);
+            }
+            Err(err) => {
+                term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
+                    .unwrap();
+                exit(1);
+            }
         }
     }
 
-    print!("{}", next_git_version);
+    print!("{}", next_git_versions.join("\n"));
 }
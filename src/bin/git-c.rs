@@ -1,6 +1,6 @@
 //! Commits a conventional commit message
 
-use std::{num::ParseIntError, process::exit};
+use std::process::exit;
 
 use clap::Parser;
 
@@ -8,9 +8,13 @@ use console::{style, Term};
 use dialoguer::{theme::ColorfulTheme, Confirm, Editor, Input, Select};
 use gitext::{
     commands::shared::{load_config, set_current_dir_from_arg},
-    conventional::ConventionalCommitMessage,
+    conventional::{
+        issue::{IssueAction, IssueRef},
+        ConventionalCommitMessage, FooterItem,
+    },
     git::{git_add, git_commit, git_push},
-    utils::StringExt,
+    gitmoji::{Gitmoji, GITMOJIS},
+    lint::Severity,
 };
 use log::debug;
 
@@ -51,12 +55,7 @@ fn main() {
             .iter()
             .map(|(k, v)| format!("{}: {}", k, v))
             .collect();
-        let commit_types_keys: Vec<_> = config
-            .commit
-            .types
-            .iter()
-            .map(|(k, _v)| k.to_string())
-            .collect();
+        let commit_types_keys: Vec<_> = config.commit.types.keys().cloned().collect();
         let select_type = Select::with_theme(&ColorfulTheme::default())
             .items(&commit_types)
             .clear(true)
@@ -81,27 +80,109 @@ fn main() {
         r#type
     };
 
+    // > gitmoji
+    let gitmoji = if config.gitmoji.enabled {
+        let gitmojis: Vec<_> = GITMOJIS
+            .iter()
+            .map(|(emoji, code, desc)| format!("{emoji} :{code}: {desc}"))
+            .collect();
+        let default_gitmoji = config
+            .gitmoji
+            .types
+            .get(&r#type)
+            .and_then(|code| GITMOJIS.iter().position(|(_, c, _)| c == code))
+            .unwrap_or(0);
+        let select_gitmoji = Select::with_theme(&ColorfulTheme::default())
+            .items(&gitmojis)
+            .clear(true)
+            .default(default_gitmoji)
+            .report(true)
+            .with_prompt("Gitmoji")
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+        match select_gitmoji {
+            Some(i) => Some(Gitmoji::new(
+                GITMOJIS[i].1,
+                config.gitmoji.position,
+                config.gitmoji.format,
+            )),
+            None => {
+                term.write_line(
+                    style("✗ A gitmoji must be selected")
+                        .red()
+                        .to_string()
+                        .as_str(),
+                )
+                .unwrap();
+                exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
     // > subject
-    let subject = Input::<String>::with_theme(&ColorfulTheme::default())
-        .with_prompt("Commit subject")
-        .report(true)
-        .interact_text()
-        .unwrap()
-        .trim()
-        .to_lowercase_first();
+    let subject = {
+        let subject = Input::<String>::with_theme(&ColorfulTheme::default())
+            .with_prompt("Commit subject")
+            .report(true)
+            .interact_text()
+            .unwrap()
+            .trim()
+            .to_string();
+        if config.lint.subject_case.level.is_on() {
+            config.lint.subject_case.value.apply(&subject)
+        } else {
+            subject
+        }
+    };
 
     // > scope
-    let scope = {
+    let scope = if config.lint.scope_enum.level.is_on() && !config.lint.scope_enum.value.is_empty()
+    {
+        // NB: allowed scopes are selected from a list
+        let mut scopes = config.lint.scope_enum.value.clone();
+        if !config.lint.scope_required.is_on() {
+            scopes.insert(0, "<none>".to_string());
+        }
+        let select_scope = Select::with_theme(&ColorfulTheme::default())
+            .items(&scopes)
+            .clear(true)
+            .default(0)
+            .report(true)
+            .with_prompt("Commit scope")
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+        match select_scope {
+            Some(i) if scopes[i] == "<none>" => None,
+            Some(i) => Some(scopes[i].clone()),
+            None => {
+                term.write_line(
+                    style("✗ A commit scope must be selected")
+                        .red()
+                        .to_string()
+                        .as_str(),
+                )
+                .unwrap();
+                exit(1);
+            }
+        }
+    } else {
         let scope: String = Input::with_theme(&ColorfulTheme::default())
             .with_prompt("Commit scope")
             .report(true)
-            .allow_empty(true)
+            .allow_empty(!config.lint.scope_required.is_on())
             .interact_text()
             .unwrap();
         if scope.is_empty() {
             None
+        } else if config.lint.scope_case.level.is_on() {
+            // NB: the case is applied to each scope
+            let case = config.lint.scope_case.value;
+            let scopes: Vec<_> = scope.split(',').map(|s| case.apply(s.trim())).collect();
+            Some(scopes.join(","))
         } else {
-            Some(scope.to_lowercase())
+            Some(scope)
         }
     };
 
@@ -159,48 +240,55 @@ fn main() {
         }
     };
 
-    // > closed issues
-    let closed_issues = {
-        match Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt("Closes issues ?")
+    // > issue references
+    let footer = {
+        let key_patterns = config.issues.key_regexes();
+        let mut footer = Vec::<FooterItem>::new();
+        let mut prompt = "Issue references ?";
+        while Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
             .report(true)
             .default(false)
             .interact()
             .unwrap()
         {
-            false => None,
-            true => {
-                let issues_str = Input::<String>::with_theme(&ColorfulTheme::default())
-                    .with_prompt("Closed issues (comma separated) ?".to_string())
-                    .report(true)
-                    .allow_empty(true)
-                    .interact_text()
-                    .unwrap();
-                match issues_str.as_str() {
-                    "" => None,
-                    s => {
-                        let issues: Result<Vec<_>, ParseIntError> = s
-                            .split(',')
-                            .enumerate()
-                            .map(|(_i, p)| p.trim().parse::<u32>())
-                            .collect();
-                        match issues {
-                            Ok(ids) => Some(ids),
-                            Err(err) => {
-                                term.write_line(
-                                    style(format!("✗ Invalid issue id: {err}"))
-                                        .red()
-                                        .to_string()
-                                        .as_str(),
-                                )
-                                .unwrap();
-                                exit(1);
-                            }
-                        }
+            let actions: Vec<_> = IssueAction::ALL.iter().map(|a| a.to_string()).collect();
+            let select_action = Select::with_theme(&ColorfulTheme::default())
+                .items(&actions)
+                .clear(true)
+                .default(0)
+                .report(true)
+                .with_prompt("Issue action")
+                .interact_on_opt(&Term::stderr())
+                .unwrap();
+            let action = match select_action {
+                Some(i) => IssueAction::ALL[i],
+                None => break,
+            };
+            let refs_str = Input::<String>::with_theme(&ColorfulTheme::default())
+                .with_prompt("Issues (eg. #12, !45, org/repo#12, PROJ-123)".to_string())
+                .report(true)
+                .allow_empty(true)
+                .interact_text()
+                .unwrap();
+            if !refs_str.trim().is_empty() {
+                match IssueRef::parse_list(action, &refs_str, &key_patterns) {
+                    Some(refs) => footer.push(FooterItem::Issues(refs)),
+                    None => {
+                        term.write_line(
+                            style(format!("✗ Invalid issue references: {refs_str}"))
+                                .red()
+                                .to_string()
+                                .as_str(),
+                        )
+                        .unwrap();
+                        exit(1);
                     }
                 }
             }
+            prompt = "Other issue references ?";
         }
+        footer
     };
 
     // write the commit message
@@ -210,19 +298,23 @@ fn main() {
         subject,
         body,
         breaking_change,
-        closed_issues,
+        footer,
+        gitmoji,
     };
 
     let commit_msg = commit.to_string();
 
     // validate the commit message
-    match ConventionalCommitMessage::parse(&commit_msg, &config.valid_commit_types()) {
-        Ok(_) => {}
-        Err(err) => {
-            term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
-                .unwrap();
-            exit(1);
-        }
+    let (_, diagnostics) = ConventionalCommitMessage::lint(&commit_msg, &config);
+    for diagnostic in &diagnostics {
+        let line = match diagnostic.severity {
+            Severity::Error => style(format!("✗ {diagnostic}")).red(),
+            Severity::Warning => style(format!("! {diagnostic}")).yellow(),
+        };
+        term.write_line(line.to_string().as_str()).unwrap();
+    }
+    if diagnostics.iter().any(|d| d.is_error()) {
+        exit(1);
     }
 
     // dry-run
@@ -10,7 +10,7 @@ use gitext::{
     changelog::ChangeLog,
     commands::shared::{load_config, set_current_dir_from_arg},
     git::git_status_porcelain,
-    version::get_repo_next_version,
+    version::{get_release_channel, get_repo_next_version, BumpOptions},
 };
 
 /// Lint command
@@ -87,9 +87,25 @@ fn main() {
         }
     };
 
+    // get the pre-release channel
+    let channel = match get_release_channel(&config, None) {
+        Ok(channel) => channel,
+        Err(err) => {
+            term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
+                .unwrap();
+            exit(1);
+        }
+    };
+
     // get the latest version based on the commit history
     // NB: can be replaced by Unreleased tag
-    let next_version = match get_repo_next_version(&config) {
+    let next_version = match get_repo_next_version(
+        &config,
+        &BumpOptions {
+            channel,
+            ..Default::default()
+        },
+    ) {
         Ok((v, _)) => v.to_string(),
         Err(err) => {
             term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
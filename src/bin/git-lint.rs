@@ -1,13 +1,19 @@
 //! Lints a commit message
 
-use std::{io, process::exit};
+use std::{
+    fs,
+    io::{self, Read},
+    process::exit,
+};
 
-use clap::Parser;
+use clap::{ArgEnum, Parser};
 
 use console::{style, Term};
 use gitext::{
     commands::shared::{load_config, set_current_dir_from_arg},
-    conventional::ConventionalCommitMessage,
+    conventional::{special::SpecialCommit, ConventionalCommitMessage},
+    git::get_config_comment_char,
+    lint::{render_diff, strip_comments, LintReport, Severity},
 };
 
 /// Lint command
@@ -20,6 +26,27 @@ pub struct Cli {
     /// Commit message (if ommitted, the message will be read from stdin)
     #[clap(short, long)]
     pub msg: Option<String>,
+    /// Commit message file (eg. the argument of the `commit-msg` hook)
+    #[clap(short, long, conflicts_with = "msg")]
+    pub file: Option<String>,
+    /// Fixes the mechanical violations (the message is rewritten in place, or printed to stdout)
+    #[clap(long)]
+    pub fix: bool,
+    /// CI mode (autosquash commits are rejected)
+    #[clap(long)]
+    pub ci: bool,
+    /// Output format
+    #[clap(long, arg_enum, default_value = "text")]
+    pub format: OutputFormat,
+}
+
+/// Output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub enum OutputFormat {
+    /// Diagnostics with code snippets
+    Text,
+    /// Parsed commit and diagnostics, as JSON (on stdout)
+    Json,
 }
 
 fn main() {
@@ -36,9 +63,23 @@ fn main() {
     let config = load_config(&cwd, true);
 
     // get the commig message
-    let commit = match &args.msg {
-        Some(c) => c.to_string(),
-        None => {
+    let mut commit = match (&args.msg, &args.file) {
+        (Some(c), _) => c.to_string(),
+        // NB: the comment lines are removed by git, after the `commit-msg` hook
+        (None, Some(file)) => match fs::read_to_string(file) {
+            Ok(c) => strip_comments(&c, &get_config_comment_char()),
+            Err(err) => {
+                term.write_line(
+                    style(format!("✗ cannot read {file}: {err}"))
+                        .red()
+                        .to_string()
+                        .as_str(),
+                )
+                .unwrap();
+                exit(1);
+            }
+        },
+        (None, None) => {
             // input is piped
             if atty::is(atty::Stream::Stdin) {
                 term.write_line(
@@ -52,30 +93,107 @@ fn main() {
             }
             let mut stdin = String::new();
             io::stdin()
-                .read_line(&mut stdin)
+                .read_to_string(&mut stdin)
                 .expect("Cannot read stdin");
             stdin
         }
     };
 
+    // fix the commit message
+    if args.fix {
+        let fixed = ConventionalCommitMessage::fix(&commit, &config);
+        if fixed == commit {
+            term.write_line(style("i Nothing to fix").blue().to_string().as_str())
+                .unwrap();
+        } else {
+            for line in render_diff(&commit, &fixed).lines() {
+                let line = match line.chars().next() {
+                    Some('-') => style(line).red(),
+                    Some('+') => style(line).green(),
+                    _ => style(line).dim(),
+                };
+                term.write_line(line.to_string().as_str()).unwrap();
+            }
+            term.write_line("").unwrap();
+        }
+        match &args.file {
+            Some(file) => {
+                if let Err(err) = fs::write(file, &fixed) {
+                    term.write_line(
+                        style(format!("✗ cannot write {file}: {err}"))
+                            .red()
+                            .to_string()
+                            .as_str(),
+                    )
+                    .unwrap();
+                    exit(1);
+                }
+            }
+            // NB: in JSON mode, stdout is the lint report (which contains the fixed commit)
+            None if args.format == OutputFormat::Text => print!("{fixed}"),
+            None => {}
+        }
+        commit = fixed;
+    }
+
     // validate the commit message
-    let _commit = match ConventionalCommitMessage::parse(&commit, &config.valid_commit_types()) {
-        Ok(c) => {
-            term.write_line(
-                format!(
-                    "{} {}",
-                    style("✔").green(),
-                    style("Conventional commit OK").bold()
-                )
-                .as_str(),
-            )
-            .unwrap();
-            c
+    let (parsed, mut diagnostics) = ConventionalCommitMessage::lint(&commit, &config);
+    if args.ci {
+        if let Some(special) = SpecialCommit::detect(&commit) {
+            diagnostics.extend(special.autosquash_diagnostic(&commit));
         }
-        Err(err) => {
-            term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
-                .unwrap();
+    }
+
+    if args.format == OutputFormat::Json {
+        let report = LintReport::new(parsed, diagnostics);
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("Cannot serialize the lint report")
+        );
+        if !report.valid {
             exit(1);
         }
-    };
+        return;
+    }
+
+    for diagnostic in &diagnostics {
+        let heading = match diagnostic.severity {
+            Severity::Error => style(diagnostic.to_string()).red().bold(),
+            Severity::Warning => style(diagnostic.to_string()).yellow().bold(),
+        };
+        term.write_line(heading.to_string().as_str()).unwrap();
+        term.write_line(
+            style(diagnostic.render_snippet(&commit))
+                .blue()
+                .to_string()
+                .as_str(),
+        )
+        .unwrap();
+        term.write_line("").unwrap();
+    }
+
+    let nb_errors = diagnostics.iter().filter(|d| d.is_error()).count();
+    if nb_errors > 0 {
+        term.write_line(
+            style(format!(
+                "✗ Invalid conventional commit ({nb_errors} error(s), {} warning(s))",
+                diagnostics.len() - nb_errors
+            ))
+            .red()
+            .to_string()
+            .as_str(),
+        )
+        .unwrap();
+        exit(1);
+    }
+
+    term.write_line(
+        format!(
+            "{} {}",
+            style("✔").green(),
+            style("Conventional commit OK").bold()
+        )
+        .as_str(),
+    )
+    .unwrap();
 }
@@ -7,9 +7,12 @@ use clap::Parser;
 use console::{style, Term};
 use gitext::{
     changelog::ChangeLog,
-    commands::shared::{load_config, set_current_dir_from_arg},
+    commands::shared::{load_config, set_current_dir_from_arg, EXIT_NO_RELEASE},
     git::{git_add, git_commit, git_push_follow_tags, git_set_tag, git_status_porcelain},
-    version::{exec_bump_commands, get_repo_next_version},
+    version::{
+        exec_bump_commands, get_release_channel, get_repo_next_version, manifest::bump_manifests,
+        tag::tag_pattern, BumpContext, BumpOptions,
+    },
 };
 
 /// Release command
@@ -31,6 +34,21 @@ pub struct Cli {
     /// If set, the RELEASENOTES.md file is not generated
     #[clap(long)]
     pub no_release_notes: bool,
+    /// Pre-release channel (eg. `beta`), overriding the channel of the current branch
+    #[clap(long)]
+    pub pre: Option<String>,
+    /// Version of the 1st release, if the repo has no version tag (defaults to 0.0.1, or the CalVer version of today)
+    #[clap(long)]
+    pub initial_version: Option<String>,
+    /// Releases 1.0.0 from a pre-1.0 version
+    #[clap(long)]
+    pub graduate: bool,
+    /// Package to release (cf. `[packages.<name>]`), can be repeated
+    #[clap(long, multiple_occurrences = true)]
+    pub package: Vec<String>,
+    /// Releases all the packages
+    #[clap(long, conflicts_with = "package")]
+    pub all_packages: bool,
 }
 
 fn main() {
@@ -46,6 +64,16 @@ fn main() {
     // load the config
     let config = load_config(&cwd, true);
 
+    // get the repo or the packages to release
+    let configs = match config.release_configs(&args.package, args.all_packages) {
+        Ok(configs) => configs,
+        Err(err) => {
+            term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
+                .unwrap();
+            exit(1);
+        }
+    };
+
     // 1. check for uncommitted changes
     match git_status_porcelain() {
         Ok(status) => {
@@ -93,8 +121,9 @@ fn main() {
         }
     };
 
-    let (next_version, prev_version) = match get_repo_next_version(&config) {
-        Ok((next_version, prev_version)) => (next_version, prev_version),
+    // get the pre-release channel
+    let channel = match get_release_channel(&config, args.pre.as_deref()) {
+        Ok(channel) => channel,
         Err(err) => {
             term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
                 .unwrap();
@@ -102,108 +131,169 @@ fn main() {
         }
     };
 
-    let (changelog_str, releasenotes_str) =
-        match changelog.generate(&config, &next_version.to_string()) {
-            Ok(s) => s,
-            Err(err) => {
-                term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
-                    .unwrap();
-                exit(1);
+    // NB: the released versions and their tags
+    let mut releases = Vec::<(String, String)>::new();
+    for config in &configs {
+        let label = config
+            .current_package()
+            .map(|(name, _)| format!("{name}: "))
+            .unwrap_or_default();
+        let fail = |err: &dyn std::fmt::Display| -> ! {
+            term.write_line(style(format!("✗ {label}{err}")).red().to_string().as_str())
+                .unwrap();
+            exit(1);
+        };
+
+        // get the tag pattern
+        let tag_pattern = match tag_pattern(config) {
+            Ok(pattern) => pattern,
+            Err(err) => fail(&err),
+        };
+
+        let (next_version, prev_version) = match get_repo_next_version(
+            config,
+            &BumpOptions {
+                channel: channel.clone(),
+                initial_version: args.initial_version.clone(),
+                graduate: args.graduate,
+            },
+        ) {
+            Ok((next_version, prev_version)) => (next_version, prev_version),
+            Err(err) if err.is_no_release() => {
+                term.write_line(
+                    style(format!("i {label}{err}"))
+                        .yellow()
+                        .to_string()
+                        .as_str(),
+                )
+                .unwrap();
+                continue;
             }
+            Err(err) => fail(&err),
         };
 
-    if !args.commit {
-        term.write_line(
-            format!(
-                "{} {}",
-                style("i").green(),
-                style(format!(
-                    "{} --> {}",
-                    prev_version
-                        .map(|v| v.to_string())
-                        .unwrap_or_else(|| "<none>".to_string()),
-                    next_version
-                ))
-                .bold()
-            )
-            .as_str(),
-        )
-        .unwrap();
-        term.write_line(
-            format!(
-                "{} {}",
-                style("i").green(),
-                style("CHANGELOG ↴".to_string()).bold()
-            )
-            .as_str(),
-        )
-        .unwrap();
-        println!("{changelog_str}");
-        exit(0);
-    }
+        let (changelog_str, releasenotes_str) =
+            match changelog.generate(config, &next_version.to_string()) {
+                Ok(s) => s,
+                Err(err) => fail(&err),
+            };
 
-    match fs::write(cwd.join("CHANGELOG.md"), changelog_str) {
-        Ok(_) => {
+        releases.push((next_version.clone(), tag_pattern.format(&next_version)));
+
+        if !args.commit {
             term.write_line(
                 format!(
                     "{} {}",
-                    style("✔").green(),
-                    style("Generated changelog").bold()
+                    style("i").green(),
+                    style(format!(
+                        "{label}{} --> {}",
+                        prev_version
+                            .map(|v| v.to_string())
+                            .unwrap_or_else(|| "<none>".to_string()),
+                        next_version
+                    ))
+                    .bold()
                 )
                 .as_str(),
             )
             .unwrap();
+            term.write_line(
+                format!(
+                    "{} {}",
+                    style("i").green(),
+                    style(format!("{label}CHANGELOG ↴")).bold()
+                )
+                .as_str(),
+            )
+            .unwrap();
+            println!("{changelog_str}");
+            continue;
         }
-        Err(err) => {
-            term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
-                .unwrap();
-            exit(1);
-        }
-    }
 
-    if !args.no_release_notes {
-        match fs::write(cwd.join("CHANGELOG_RELEASE.md"), releasenotes_str) {
+        let changelog_file = config.changelog.changelog_file();
+        match fs::write(cwd.join(&changelog_file), changelog_str) {
             Ok(_) => {
                 term.write_line(
                     format!(
                         "{} {}",
                         style("✔").green(),
-                        style("Generated release notes").bold()
+                        style(format!("Generated changelog {}", changelog_file.display())).bold()
                     )
                     .as_str(),
                 )
                 .unwrap();
             }
-            Err(err) => {
-                term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
+            Err(err) => fail(&err),
+        }
+
+        if !args.no_release_notes {
+            let release_notes_file = config.changelog.release_notes_file();
+            match fs::write(cwd.join(&release_notes_file), releasenotes_str) {
+                Ok(_) => {
+                    term.write_line(
+                        format!(
+                            "{} {}",
+                            style("✔").green(),
+                            style(format!(
+                                "Generated release notes {}",
+                                release_notes_file.display()
+                            ))
+                            .bold()
+                        )
+                        .as_str(),
+                    )
                     .unwrap();
-                exit(1);
+                }
+                Err(err) => fail(&err),
             }
         }
-    }
 
-    // 3. Bump the package versions
-    match exec_bump_commands(&config, &next_version.to_string()) {
-        Ok(exec_commands) => {
-            for cmd in exec_commands {
-                term.write_line(
-                    format!(
-                        "{} {}",
-                        style("✔").green(),
-                        style(format!("Executed bump command: {cmd} ")).bold()
+        // 3. Bump the package versions
+        match bump_manifests(config, &next_version) {
+            Ok(changed_files) => {
+                for file in changed_files {
+                    term.write_line(
+                        format!(
+                            "{} {}",
+                            style("✔").green(),
+                            style(format!("Bumped version in {} ", file.display())).bold()
+                        )
+                        .as_str(),
                     )
-                    .as_str(),
-                )
-                .unwrap();
+                    .unwrap();
+                }
             }
+            Err(err) => fail(&err),
         }
-        Err(err) => {
-            term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
-                .unwrap();
-            exit(1);
+        let context = match BumpContext::new(config, &next_version, prev_version.as_deref()) {
+            Ok(context) => context,
+            Err(err) => fail(&err),
+        };
+        match exec_bump_commands(config, &context) {
+            Ok(exec_commands) => {
+                for cmd in exec_commands {
+                    term.write_line(
+                        format!(
+                            "{} {}",
+                            style("✔").green(),
+                            style(format!("Executed bump command: {cmd} ")).bold()
+                        )
+                        .as_str(),
+                    )
+                    .unwrap();
+                }
+            }
+            Err(err) => fail(&err),
         }
     }
 
+    if releases.is_empty() {
+        exit(EXIT_NO_RELEASE);
+    }
+    if !args.commit {
+        exit(0);
+    }
+
     // 4. Commit the changes
     match git_add() {
         Ok(_) => {
@@ -219,14 +309,23 @@ fn main() {
         }
     }
 
-    let commit_msg = format!("chore(release): created release {}", next_version);
+    // NB: the releases of packages are identified by their tag
+    let release_names = match releases.as_slice() {
+        [(next_version, _)] if config.packages.is_empty() => next_version.clone(),
+        _ => releases
+            .iter()
+            .map(|(_, tag)| tag.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+    };
+    let commit_msg = format!("chore(release): created release {}", release_names);
     match git_commit(&commit_msg) {
         Ok(_) => {
             term.write_line(
                 format!(
                     "{} {}",
                     style("✔").green(),
-                    style(format!("Created commit for release {next_version} ")).bold()
+                    style(format!("Created commit for release {release_names} ")).bold()
                 )
                 .as_str(),
             )
@@ -240,26 +339,27 @@ fn main() {
     }
 
     // 5. Tag the commit
-    let new_git_tag = format!("v{}", next_version);
-    match git_set_tag(
-        new_git_tag.as_str(),
-        format!("Release {next_version}").as_str(),
-    ) {
-        Ok(_) => {
-            term.write_line(
-                format!(
-                    "{} {}",
-                    style("✔").green(),
-                    style(format!("Tagged commit as {new_git_tag} ")).bold()
+    for (next_version, new_git_tag) in &releases {
+        match git_set_tag(
+            new_git_tag.as_str(),
+            format!("Release {next_version}").as_str(),
+        ) {
+            Ok(_) => {
+                term.write_line(
+                    format!(
+                        "{} {}",
+                        style("✔").green(),
+                        style(format!("Tagged commit as {new_git_tag} ")).bold()
+                    )
+                    .as_str(),
                 )
-                .as_str(),
-            )
-            .unwrap();
-        }
-        Err(err) => {
-            term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
                 .unwrap();
-            exit(1);
+            }
+            Err(err) => {
+                term.write_line(style(format!("✗ {err}")).red().to_string().as_str())
+                    .unwrap();
+                exit(1);
+            }
         }
     }
 
@@ -6,19 +6,25 @@
 //!
 //! Refer to [https://keepachangelog.com/en/1.0.0/](https://keepachangelog.com/en/1.0.0/)
 
+use std::collections::HashSet;
+
 use chrono::Utc;
 use handlebars::Handlebars;
 use indoc::indoc;
-use log::warn;
+use log::{debug, warn};
+use semver::Version;
 use serde::Serialize;
 
 use crate::{
-    config::Config,
-    conventional::ConventionalCommitMessage,
-    error::Result,
+    config::{ChangeLogScopes, Config},
+    conventional::{ConventionalCommitMessage, Trailer},
+    error::{Error, Result},
     git::{get_config_origin_url, git_get_tags, git_log},
     utils::StringExt,
-    version::IntoSemverGitTags,
+    version::{
+        get_cancelled_commits, get_package_commit_ids, scheme::version_scheme, tag::tag_pattern,
+        IntoSemverGitTags,
+    },
 };
 
 /// Changelog template
@@ -36,9 +42,19 @@ const CHANGELOG_TEMPLATE: &str = indoc!(
 
     {{#each this.groups}}
     ### {{this.title}}
+    {{#if this.commits}}
+
+    {{#each this.commits}}
+    - {{#if this.breaking_change}}**BREAKING** {{/if}}{{this.prefix}}{{this.subject}} {{this.commit_link}}{{#each this.issues}} {{this}}{{/each}}
+    {{/each}}
+    {{/if}}
+    {{#each this.scopes}}
+
+    #### {{this.scope}}
 
     {{#each this.commits}}
-    - {{this.prefix}}{{this.subject}} {{this.commit_link}}
+    - {{#if this.breaking_change}}**BREAKING** {{/if}}{{this.prefix}}{{this.subject}} {{this.commit_link}}{{#each this.issues}} {{this}}{{/each}}
+    {{/each}}
     {{/each}}
 
     {{/each}}
@@ -47,13 +63,23 @@ const CHANGELOG_TEMPLATE: &str = indoc!(
 
 /// Release Notes template
 const RELEASENOTES_TEMPLATE: &str = indoc!(
-    "Release notes for `v{{this.version}}`
+    "Release notes for `{{this.tag}}`
     
     {{#each this.groups}}
     ### {{this.title}}
+    {{#if this.commits}}
 
     {{#each this.commits}}
-    - {{this.prefix}}{{this.subject}} {{this.commit_link}}
+    - {{#if this.breaking_change}}**BREAKING** {{/if}}{{this.prefix}}{{this.subject}} {{this.commit_link}}{{#each this.issues}} {{this}}{{/each}}
+    {{/each}}
+    {{/if}}
+    {{#each this.scopes}}
+
+    #### {{this.scope}}
+
+    {{#each this.commits}}
+    - {{#if this.breaking_change}}**BREAKING** {{/if}}{{this.prefix}}{{this.subject}} {{this.commit_link}}{{#each this.issues}} {{this}}{{/each}}
+    {{/each}}
     {{/each}}
 
     {{/each}}
@@ -67,6 +93,18 @@ struct ChangeLogCommit {
     prefix: String,
     subject: String,
     commit_link: String,
+    breaking_change: Option<String>,
+    trailers: Vec<Trailer>,
+    scopes: Vec<String>,
+    /// Issue references, as markdown links if the URL is known
+    issues: Vec<String>,
+}
+
+/// Changelog scope group
+#[derive(Debug, Clone, Serialize)]
+struct ChangeLogScopeGroup {
+    scope: String,
+    commits: Vec<ChangeLogCommit>,
 }
 
 /// Changelog release group
@@ -75,6 +113,7 @@ struct ChangeLogReleaseGroup {
     key: String,
     title: String,
     commits: Vec<ChangeLogCommit>,
+    scopes: Vec<ChangeLogScopeGroup>,
 }
 
 /// Changelog release
@@ -102,6 +141,8 @@ struct ChangeLogData {
 struct ReleaseNotesData {
     /// Release version
     version: String,
+    /// Release tag
+    tag: String,
     /// Commits groups
     groups: Vec<ChangeLogReleaseGroup>,
 }
@@ -138,53 +179,159 @@ impl ChangeLog {
         });
 
         // read all logs from the start of the repository (latest to earliest)
+        // NB: for a package, the commits of other packages are only used to locate the tags
         let commits = git_log("")?;
+        let package_commits = get_package_commit_ids(config, &commits);
 
         // read all tags from the repository
-        let tags = git_get_tags()?.into_semver()?;
+        // NB: pre-release tags are merged into their stable release (eg. `1.3.0-beta.1` into `1.3.0`)
+        let scheme = version_scheme(config)?;
+        let pattern = tag_pattern(config)?;
+        let tags = git_get_tags()?.into_versions(&pattern, scheme.as_ref());
+        let stable_versions: Vec<Version> = tags
+            .iter()
+            .map(|t| t.version.clone())
+            .chain(scheme.parse(next_version))
+            .filter(|v| v.pre.is_empty())
+            .collect();
+        let tags: Vec<_> = tags
+            .into_iter()
+            .filter(|t| {
+                t.version.pre.is_empty()
+                    || !stable_versions.iter().any(|v| {
+                        (v.major, v.minor, v.patch)
+                            == (t.version.major, t.version.minor, t.version.patch)
+                    })
+            })
+            .collect();
 
         // Origin URL
         let origin_url = get_config_origin_url()?;
 
-        for c in commits {
+        // NB: aliases are resolved so that they match the canonical type of the parsed commits
+        let changelog_types: Vec<&str> = config
+            .changelog
+            .types
+            .iter()
+            .map(|t| config.canonical_commit_type(t))
+            .collect();
+
+        let parsed_commits: Vec<_> = commits
+            .iter()
+            .map(|c| ConventionalCommitMessage::parse_log_commit(c, config))
+            .collect();
+
+        // NB: a commit and its revert cancel out if they are part of the same release
+        let mut cancelled = HashSet::<String>::new();
+        let mut release_commits = Vec::<(&str, Option<&str>)>::new();
+        for (c, parsed) in commits.iter().zip(&parsed_commits) {
+            if tags.iter().any(|t| t.tag.commit_hash == c.id) {
+                cancelled.extend(get_cancelled_commits(&release_commits));
+                release_commits.clear();
+            }
+            let reverted = match parsed {
+                Ok(Some(p)) => p.reverted_commit(),
+                _ => None,
+            };
+            release_commits.push((&c.id, reverted));
+        }
+        cancelled.extend(get_cancelled_commits(&release_commits));
+
+        for (c, parsed) in commits.iter().zip(parsed_commits) {
             // eprintln!("{:#?}", c);
 
+            // NB: the tag is checked first, as the commit may be excluded
+            let commit_tag = tags.iter().find(|t| t.tag.commit_hash == c.id);
+            if let Some(t) = commit_tag {
+                // commit has a tag which means that it belongs to another version
+                data.releases.push(ChangeLogRelease {
+                    version: scheme.format(&t.version),
+                    date: t.tag.date.format("%Y-%m-%d").to_string(),
+                    history_url: "".to_string(),
+                    groups: vec![],
+                });
+            }
+
+            if let Some(ids) = &package_commits {
+                if !ids.contains(&c.id) {
+                    debug!("Commit ({}) does not belong to the package", c.id);
+                    continue;
+                }
+            }
+
+            if cancelled.contains(&c.id) {
+                debug!("Commit ({}) is cancelled out by a revert", c.id);
+                continue;
+            }
+
             // > get type and subject from the message
-            let changelog_commit =
-                match ConventionalCommitMessage::parse(&c.message, &config.valid_commit_types()) {
-                    Ok(conv_msg) => {
-                        let mut short_hash = c.id.clone();
-                        short_hash.truncate(5);
-                        let commit_url = format!("{}/commit/{}", origin_url, c.id);
-                        let commit_link = format!("[#{}]({})", short_hash, commit_url);
-
-                        ChangeLogCommit {
-                            r#type: conv_msg.r#type.clone(),
-                            prefix: "".to_string(),
-                            // prefix: conv_msg
-                            //     .scope
-                            //     .map(|s| format!("{}: ", s))
-                            //     .unwrap_or_default(),
-                            subject: conv_msg.subject.clone().to_uppercase_first(),
-                            commit_link,
+            let changelog_commit = match parsed {
+                Ok(None) => {
+                    debug!("Commit ({}) is skipped", c.id);
+                    continue;
+                }
+                Ok(Some(conv_msg)) => {
+                    let mut short_hash = c.id.clone();
+                    short_hash.truncate(5);
+                    let commit_url = format!("{}/commit/{}", origin_url, c.id);
+                    let commit_link = format!("[#{}]({})", short_hash, commit_url);
+
+                    let scopes: Vec<String> =
+                        conv_msg.scopes().iter().map(|s| s.to_string()).collect();
+                    let prefix = match config.changelog.scopes {
+                        ChangeLogScopes::Prefix if !scopes.is_empty() => {
+                            format!("**{}:** ", scopes.join(", "))
                         }
+                        _ => "".to_string(),
+                    };
+
+                    ChangeLogCommit {
+                        r#type: conv_msg.r#type.clone(),
+                        prefix,
+                        subject: match &conv_msg.gitmoji {
+                            Some(g) if config.changelog.show_gitmoji => format!(
+                                "{} {}",
+                                g.emoji().unwrap_or_default(),
+                                conv_msg.subject.to_uppercase_first()
+                            ),
+                            _ => conv_msg.subject.clone().to_uppercase_first(),
+                        },
+                        commit_link,
+                        breaking_change: conv_msg
+                            .breaking_change_description()
+                            .map(|b| b.to_string()),
+                        trailers: conv_msg.trailers().into_iter().cloned().collect(),
+                        scopes,
+                        issues: conv_msg
+                            .issues()
+                            .into_iter()
+                            .map(|i| match i.url(&origin_url, &config.issues) {
+                                Some(url) => format!("[{i}]({url})"),
+                                None => i.to_string(),
+                            })
+                            .collect(),
                     }
-                    Err(err) => {
-                        // NB: add as a specific group
-                        let mut short_id = c.id.clone();
-                        short_id.truncate(7);
-                        warn!("Commit ({}) is unconventional ({})", short_id, err);
-                        let commit_msg_first_line = c.message.lines().next().unwrap();
-                        ChangeLogCommit {
-                            r#type: "uncategorized".to_string(),
-                            prefix: "".to_string(),
-                            subject: commit_msg_first_line.to_string(),
-                            commit_link: "".to_string(),
-                        }
+                }
+                Err(err) => {
+                    // NB: add as a specific group
+                    let mut short_id = c.id.clone();
+                    short_id.truncate(7);
+                    warn!("Commit ({}) is unconventional ({})", short_id, err);
+                    let commit_msg_first_line = c.message.lines().next().unwrap();
+                    ChangeLogCommit {
+                        r#type: "uncategorized".to_string(),
+                        prefix: "".to_string(),
+                        subject: commit_msg_first_line.to_string(),
+                        commit_link: "".to_string(),
+                        breaking_change: None,
+                        trailers: vec![],
+                        scopes: vec![],
+                        issues: vec![],
                     }
-                };
+                }
+            };
 
-            let type_title = if config.changelog.types.contains(&changelog_commit.r#type) {
+            let type_title = if changelog_types.contains(&changelog_commit.r#type.as_str()) {
                 match config.commit.types.get(&changelog_commit.r#type) {
                     Some(x) => x.clone(),
                     None => changelog_commit.r#type.clone(),
@@ -194,45 +341,49 @@ impl ChangeLog {
                 continue;
             };
 
-            let commit_tag = tags.iter().find(|t| t.tag.commit_hash == c.id);
-            match commit_tag {
-                Some(t) => {
-                    // commit has a tag which means that it belongs to another version
-                    data.releases.push(ChangeLogRelease {
-                        version: t.version.to_string(),
-                        date: t.tag.date.format("%Y-%m-%d").to_string(),
-                        history_url: "".to_string(),
-                        groups: vec![],
-                    });
-                }
-                None => {}
-            }
-
             // add release for that commit
             let release = data.releases.last_mut().unwrap();
 
-            if let Some(group) = release
+            let group = match release
                 .groups
                 .iter_mut()
-                .find(|g| g.key == changelog_commit.r#type)
+                .position(|g| g.key == changelog_commit.r#type)
             {
-                group.commits.push(changelog_commit);
-            } else {
-                // New group
-                let group_title = if changelog_commit.r#type == "uncategorized" {
-                    "Uncategorized".to_string()
-                } else {
-                    type_title
-                };
-
-                let group = ChangeLogReleaseGroup {
-                    key: changelog_commit.r#type.clone(),
-                    title: group_title,
-                    commits: vec![changelog_commit],
-                };
-
-                release.groups.push(group);
+                Some(i) => &mut release.groups[i],
+                None => {
+                    // New group
+                    let group_title = if changelog_commit.r#type == "uncategorized" {
+                        "Uncategorized".to_string()
+                    } else {
+                        type_title
+                    };
+
+                    release.groups.push(ChangeLogReleaseGroup {
+                        key: changelog_commit.r#type.clone(),
+                        title: group_title,
+                        commits: vec![],
+                        scopes: vec![],
+                    });
+                    release.groups.last_mut().unwrap()
+                }
             };
+
+            if config.changelog.scopes == ChangeLogScopes::Group
+                && !changelog_commit.scopes.is_empty()
+            {
+                // NB: a commit with several scopes is listed under each scope
+                for scope in &changelog_commit.scopes {
+                    match group.scopes.iter_mut().find(|g| &g.scope == scope) {
+                        Some(scope_group) => scope_group.commits.push(changelog_commit.clone()),
+                        None => group.scopes.push(ChangeLogScopeGroup {
+                            scope: scope.clone(),
+                            commits: vec![changelog_commit.clone()],
+                        }),
+                    }
+                }
+            } else {
+                group.commits.push(changelog_commit);
+            }
         }
 
         // debug
@@ -247,28 +398,26 @@ impl ChangeLog {
                 let ref_end = if release.version == "Unreleased" {
                     "HEAD".to_string()
                 } else {
-                    release.version.clone()
+                    pattern.format(&release.version)
                 };
                 release.history_url = format!("{}/compare/{}...{}", origin_url, ref_start, ref_end);
             }
-            from_ref = Some(release.version.clone());
+            from_ref = Some(pattern.format(&release.version));
 
             // sort
-            release.groups.sort_by(|g1, g2| {
-                let i1 = config
-                    .changelog
-                    .types
-                    .iter()
-                    .position(|cfg_type| cfg_type == &g1.key)
-                    .expect("Invalid type");
-                let i2 = config
-                    .changelog
-                    .types
-                    .iter()
-                    .position(|cfg_type| cfg_type == &g2.key)
-                    .expect("Invalid type");
-                i1.cmp(&i2)
-            });
+            let mut groups = release
+                .groups
+                .drain(..)
+                .map(|g| match changelog_types.iter().position(|t| *t == g.key) {
+                    Some(i) => Ok((i, g)),
+                    None => Err(Error::InternalError(format!(
+                        "Type '{}' is not in the changelog types",
+                        g.key
+                    ))),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            groups.sort_by_key(|(i, _)| *i);
+            release.groups = groups.into_iter().map(|(_, g)| g).collect();
         }
 
         // render changelog
@@ -278,6 +427,7 @@ impl ChangeLog {
         let this_release = data.releases.first().unwrap();
         let release_notes_date = ReleaseNotesData {
             version: this_release.version.clone(),
+            tag: pattern.format(&this_release.version),
             groups: this_release.groups.to_vec(),
         };
         let releasenotes = self.registry.render("releasenotes", &release_notes_date)?;
@@ -12,6 +12,9 @@ use log::debug;
 
 use crate::config::Config;
 
+/// Exit code when there is nothing to release (cf. [Error::is_no_release](crate::error::Error::is_no_release))
+pub const EXIT_NO_RELEASE: i32 = 3;
+
 /// Sets the current directory from an argument
 pub fn set_current_dir_from_arg(cwd_input: &Option<String>) -> PathBuf {
     let term = Term::stderr();
@@ -2,13 +2,24 @@
 
 use std::{
     collections::BTreeMap,
+    fmt::Display,
     fs,
     path::{Path, PathBuf},
 };
 
-use serde::{Deserialize, Serialize};
+use log::debug;
+use regex::Regex;
+use serde::{Deserialize, Serialize, Serializer};
 
-use crate::error::Result;
+use crate::{
+    error::{Error, Result},
+    gitmoji::{GitmojiFormat, GitmojiPosition},
+    lint::{Case, LintLevel, LintRule},
+};
+
+pub mod commitlint;
+
+use commitlint::CommitlintConfig;
 
 /// Configuration directory
 pub const CONFIG_DIR: &str = ".gitx";
@@ -16,6 +27,15 @@ pub const CONFIG_DIR: &str = ".gitx";
 /// Configuration file name
 pub const CONFIG_FILE: &str = "config.toml";
 
+/// Default changelog file name
+pub const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+/// Release notes file name
+pub const RELEASE_NOTES_FILE: &str = "CHANGELOG_RELEASE.md";
+
+/// Default tag pattern of the packages (cf. `[packages.<name>]`)
+pub const DEFAULT_PACKAGE_TAG_PATTERN: &str = "{package}@{version}";
+
 /// Commits configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommitsConfig {
@@ -23,6 +43,18 @@ pub struct CommitsConfig {
     pub types_inc_minor: Vec<String>,
     /// Commit types (key + description)
     pub types: BTreeMap<String, String>,
+    /// Commit type aliases (eg. `feature` for `feat`)
+    #[serde(default = "CommitsConfig::default_aliases")]
+    pub aliases: BTreeMap<String, String>,
+    /// Version increment per type, or per type and scope (eg. `docs(api)`)
+    ///
+    /// NB: types without rule fall back to `types_inc_minor`, then to the built-in increments
+    /// (cf. [CommitsConfig::default_type_bump]).
+    #[serde(default)]
+    pub bump: BTreeMap<String, Bump>,
+    /// Policies for the merge and autosquash commits
+    #[serde(default)]
+    pub policies: CommitPoliciesConfig,
 }
 
 impl Default for CommitsConfig {
@@ -39,12 +71,212 @@ impl Default for CommitsConfig {
         types.insert("ci".to_string(), "Continuous Integration".to_string());
         types.insert("cd".to_string(), "Continuous Delivery".to_string());
         types.insert("chore".to_string(), "Other changes".to_string());
+        types.insert("revert".to_string(), "Reverts".to_string());
 
         let types_inc_minor = vec!["feat".to_string()];
 
         Self {
             types,
             types_inc_minor,
+            aliases: Self::default_aliases(),
+            bump: BTreeMap::new(),
+            policies: CommitPoliciesConfig::default(),
+        }
+    }
+}
+
+impl CommitsConfig {
+    /// Returns the default commit type aliases
+    fn default_aliases() -> BTreeMap<String, String> {
+        let mut aliases = BTreeMap::new();
+        aliases.insert("feature".to_string(), "feat".to_string());
+        aliases.insert("bugfix".to_string(), "fix".to_string());
+        aliases.insert("doc".to_string(), "docs".to_string());
+        aliases
+    }
+
+    /// Returns the built-in version increment of a commit type
+    ///
+    /// The commits which are not user-facing (`docs`, `style`, `refactor`, `test`, `build`, `ci`, `cd`
+    /// and `chore`) do not bump the version, other types are patch increments.
+    pub fn default_type_bump(commit_type: &str) -> Bump {
+        match commit_type {
+            "docs" | "style" | "refactor" | "test" | "build" | "ci" | "cd" | "chore" => Bump::None,
+            _ => Bump::Patch,
+        }
+    }
+}
+
+/// Version increment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Bump {
+    /// No release
+    None,
+    /// Patch increment
+    Patch,
+    /// Minor increment
+    Minor,
+    /// Major increment
+    Major,
+}
+
+impl Display for Bump {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Bump::None => write!(f, "none"),
+            Bump::Patch => write!(f, "patch"),
+            Bump::Minor => write!(f, "minor"),
+            Bump::Major => write!(f, "major"),
+        }
+    }
+}
+
+/// Policy for the merge and autosquash commits in the history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommitPolicy {
+    /// The commit is skipped
+    Skip,
+    /// The autosquash commit is treated as the commit it references (eg. `fixup! feat: add feature`)
+    Referenced,
+    /// The 1st line of the merge commit body (eg. the PR title) is parsed as the commit
+    PrTitle,
+}
+
+/// Policies for the merge and autosquash commits
+///
+/// NB: `referenced` only applies to autosquash commits, and `pr-title` to merge commits
+/// (cf. [CommitPoliciesConfig::validate]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommitPoliciesConfig {
+    /// Merge commits
+    pub merge: CommitPolicy,
+    /// `fixup!` commits
+    pub fixup: CommitPolicy,
+    /// `squash!` commits
+    pub squash: CommitPolicy,
+    /// `amend!` commits
+    pub amend: CommitPolicy,
+}
+
+impl Default for CommitPoliciesConfig {
+    fn default() -> Self {
+        Self {
+            merge: CommitPolicy::Skip,
+            fixup: CommitPolicy::Skip,
+            squash: CommitPolicy::Skip,
+            amend: CommitPolicy::Skip,
+        }
+    }
+}
+
+impl CommitPoliciesConfig {
+    /// Checks that each policy applies to its kind of commit
+    pub fn validate(&self) -> Result<()> {
+        if self.merge == CommitPolicy::Referenced {
+            return Err(Error::InternalError(
+                "Invalid config: the 'referenced' policy does not apply to merge commits (commit.policies.merge)"
+                    .to_string(),
+            ));
+        }
+        for (name, policy) in [
+            ("fixup", self.fixup),
+            ("squash", self.squash),
+            ("amend", self.amend),
+        ] {
+            if policy == CommitPolicy::PrTitle {
+                return Err(Error::InternalError(format!(
+                    "Invalid config: the 'pr-title' policy does not apply to autosquash commits (commit.policies.{name})"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Issue references configuration
+///
+/// In the URL templates, `{{repo_url}}` is replaced by the repository URL,
+/// `{{id}}` by the issue number, and `{{key}}` by the tracker key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IssuesConfig {
+    /// Patterns of the issue tracker keys (eg. `[A-Z][A-Z0-9]+-[0-9]+` for `PROJ-123`)
+    pub key_patterns: Vec<String>,
+    /// URL template of the issues (`#12`)
+    pub issue_url: String,
+    /// URL template of the merge requests (`!45`)
+    pub merge_request_url: String,
+    /// URL template of the tracker keys (eg. `https://jira.example.com/browse/{{key}}`)
+    pub key_url: Option<String>,
+}
+
+impl Default for IssuesConfig {
+    fn default() -> Self {
+        Self {
+            key_patterns: vec!["[A-Z][A-Z0-9]+-[0-9]+".to_string()],
+            issue_url: "{{repo_url}}/issues/{{id}}".to_string(),
+            merge_request_url: "{{repo_url}}/-/merge_requests/{{id}}".to_string(),
+            key_url: None,
+        }
+    }
+}
+
+impl IssuesConfig {
+    /// Returns the key patterns, matching whole references
+    ///
+    /// NB: invalid patterns are ignored.
+    pub fn key_regexes(&self) -> Vec<Regex> {
+        self.key_patterns
+            .iter()
+            .filter_map(|p| match Regex::new(&format!("^(?:{p})$")) {
+                Ok(regex) => Some(regex),
+                Err(err) => {
+                    log::warn!("Invalid issue key pattern '{p}' ({err})");
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// Gitmoji configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitmojiConfig {
+    /// Enables the gitmoji mode
+    pub enabled: bool,
+    /// Position of the gitmoji in new commits
+    pub position: GitmojiPosition,
+    /// Format of the gitmoji in new commits
+    pub format: GitmojiFormat,
+    /// Default gitmoji shortcode for each commit type
+    pub types: BTreeMap<String, String>,
+}
+
+impl Default for GitmojiConfig {
+    fn default() -> Self {
+        let mut types = BTreeMap::new();
+        types.insert("feat".to_string(), "sparkles".to_string());
+        types.insert("fix".to_string(), "bug".to_string());
+        types.insert("docs".to_string(), "memo".to_string());
+        types.insert("style".to_string(), "art".to_string());
+        types.insert("refactor".to_string(), "recycle".to_string());
+        types.insert("perf".to_string(), "zap".to_string());
+        types.insert("test".to_string(), "white_check_mark".to_string());
+        types.insert("build".to_string(), "package".to_string());
+        types.insert("ci".to_string(), "construction_worker".to_string());
+        types.insert("cd".to_string(), "rocket".to_string());
+        types.insert("chore".to_string(), "wrench".to_string());
+        types.insert("revert".to_string(), "rewind".to_string());
+
+        Self {
+            enabled: false,
+            position: GitmojiPosition::default(),
+            format: GitmojiFormat::default(),
+            types,
         }
     }
 }
@@ -54,6 +286,41 @@ impl Default for CommitsConfig {
 pub struct ChangeLogConfig {
     /// Types to include in the changelog
     pub types: Vec<String>,
+    /// If set, the commits gitmoji are shown in the changelog
+    #[serde(default)]
+    pub show_gitmoji: bool,
+    /// Display of the commits scopes
+    #[serde(default)]
+    pub scopes: ChangeLogScopes,
+    /// Changelog file, relative to the repo root (defaults to `CHANGELOG.md`)
+    pub file: Option<PathBuf>,
+}
+
+impl ChangeLogConfig {
+    /// Returns the changelog file, relative to the repo root
+    pub fn changelog_file(&self) -> PathBuf {
+        self.file
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(CHANGELOG_FILE))
+    }
+
+    /// Returns the release notes file, next to the changelog file
+    pub fn release_notes_file(&self) -> PathBuf {
+        self.changelog_file().with_file_name(RELEASE_NOTES_FILE)
+    }
+}
+
+/// Display of the commits scopes in the changelog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeLogScopes {
+    /// Scopes are not shown
+    #[default]
+    Hidden,
+    /// Entries are prefixed with their scopes (eg. `**ui, api:** Add button`)
+    Prefix,
+    /// Entries are grouped by scope within each type (an entry with several scopes is listed under each)
+    Group,
 }
 
 impl Default for ChangeLogConfig {
@@ -68,17 +335,274 @@ impl Default for ChangeLogConfig {
             "ci".to_string(),
             "cd".to_string(),
             "chore".to_string(),
+            "revert".to_string(),
         ];
 
-        Self { types }
+        Self {
+            types,
+            show_gitmoji: false,
+            scopes: ChangeLogScopes::default(),
+            file: None,
+        }
+    }
+}
+
+/// Lint configuration
+///
+/// Each rule can be turned off, or report a warning or an error.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LintConfig {
+    /// Body must be separated from the header by an empty line
+    pub body_leading_blank: LintLevel,
+    /// Type must be lowercase
+    pub type_case: LintLevel,
+    /// Scope is required
+    pub scope_required: LintLevel,
+    /// Subject must not end with a period
+    pub subject_full_stop: LintLevel,
+    /// Closing trailers (eg. `Fixes:`) must reference issues
+    ///
+    /// NB: the levels are declared before the rules with a value, which are serialized as tables.
+    pub footer_issue: LintLevel,
+    /// Scope case
+    pub scope_case: LintRule<Case>,
+    /// Subject case
+    pub subject_case: LintRule<Case>,
+    /// Maximum header length
+    pub header_max_length: LintRule<usize>,
+    /// Maximum length of the lines after the header
+    pub body_max_line_length: LintRule<usize>,
+    /// Allowed scopes
+    pub scope_enum: LintRule<Vec<String>>,
+    /// Commit types requiring a body
+    pub body_required_types: LintRule<Vec<String>>,
+    /// Commit types requiring a footer
+    pub footer_required_types: LintRule<Vec<String>>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            body_leading_blank: LintLevel::Error,
+            type_case: LintLevel::Error,
+            scope_required: LintLevel::Off,
+            subject_full_stop: LintLevel::Off,
+            footer_issue: LintLevel::Warn,
+            scope_case: LintRule::new(LintLevel::Error, Case::LowerFirst),
+            subject_case: LintRule::new(LintLevel::Error, Case::LowerFirst),
+            header_max_length: LintRule::new(LintLevel::Off, 72),
+            body_max_line_length: LintRule::new(LintLevel::Off, 100),
+            scope_enum: LintRule::new(LintLevel::Off, vec![]),
+            body_required_types: LintRule::new(LintLevel::Off, vec![]),
+            footer_required_types: LintRule::new(LintLevel::Off, vec![]),
+        }
+    }
+}
+
+impl LintConfig {
+    /// Returns the config with all the rules turned off
+    ///
+    /// Only the structure of the commit messages is checked (eg. to parse the history).
+    pub fn disabled() -> Self {
+        let default = Self::default();
+        Self {
+            body_leading_blank: LintLevel::Off,
+            type_case: LintLevel::Off,
+            scope_required: LintLevel::Off,
+            subject_full_stop: LintLevel::Off,
+            footer_issue: LintLevel::Off,
+            scope_case: LintRule::new(LintLevel::Off, default.scope_case.value),
+            subject_case: LintRule::new(LintLevel::Off, default.subject_case.value),
+            header_max_length: LintRule::new(LintLevel::Off, default.header_max_length.value),
+            body_max_line_length: LintRule::new(LintLevel::Off, default.body_max_line_length.value),
+            scope_enum: LintRule::new(LintLevel::Off, vec![]),
+            body_required_types: LintRule::new(LintLevel::Off, vec![]),
+            footer_required_types: LintRule::new(LintLevel::Off, vec![]),
+        }
+    }
+}
+
+/// Version scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionSchemeKind {
+    /// Semantic versioning (eg. `1.3.0`)
+    #[default]
+    Semver,
+    /// Calendar versioning (eg. `2024.05.2`)
+    Calver,
+}
+
+/// Versioning policy before 1.0.0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PreStablePolicy {
+    /// Semver rules (a breaking change releases 1.0.0)
+    Strict,
+    /// Breaking changes are minor increments, and other changes are patch increments
+    #[default]
+    BreakingMinor,
+}
+
+/// Manifest kind, for the version bumpers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestKind {
+    /// `Cargo.toml` (with the workspace members and `Cargo.lock`)
+    Cargo,
+    /// `package.json` or `package-lock.json`
+    Npm,
+    /// `pyproject.toml` (PEP 621 or Poetry)
+    Pyproject,
+    /// Helm `Chart.yaml`
+    Helm,
+    /// Maven `pom.xml`
+    Maven,
+    /// Any file, with a version regex
+    Regex,
+}
+
+impl ManifestKind {
+    /// Infers the manifest kind from the file name
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.file_name()?.to_str()? {
+            "Cargo.toml" => Some(ManifestKind::Cargo),
+            "package.json" | "package-lock.json" => Some(ManifestKind::Npm),
+            "pyproject.toml" => Some(ManifestKind::Pyproject),
+            "Chart.yaml" => Some(ManifestKind::Helm),
+            "pom.xml" => Some(ManifestKind::Maven),
+            _ => None,
+        }
+    }
+}
+
+/// File whose version is bumped on release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BumpFile {
+    /// Path, relative to the repo root
+    pub path: PathBuf,
+    /// Manifest kind (inferred from the file name by default)
+    pub kind: Option<ManifestKind>,
+    /// Version regex, with a `version` group, for the `regex` kind (eg. `VERSION = "(?P<version>.*)"`)
+    pub pattern: Option<String>,
+}
+
+impl BumpFile {
+    /// Returns the manifest kind of the file
+    pub fn kind(&self) -> Option<ManifestKind> {
+        match self.kind {
+            Some(kind) => Some(kind),
+            None if self.pattern.is_some() => Some(ManifestKind::Regex),
+            None => ManifestKind::from_path(&self.path),
+        }
+    }
+}
+
+/// Command executed when the version is bumped, as a string or a table with the execution settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BumpCommand {
+    /// Command (eg. `cargo update`)
+    Command(String),
+    /// Command with execution settings
+    Detailed(BumpCommandConfig),
+}
+
+impl BumpCommand {
+    /// Returns the command settings
+    pub fn config(&self) -> BumpCommandConfig {
+        match self {
+            BumpCommand::Command(cmd) => BumpCommandConfig {
+                cmd: cmd.clone(),
+                ..Default::default()
+            },
+            BumpCommand::Detailed(config) => config.clone(),
+        }
     }
 }
 
+/// Serializes the bump commands
+///
+/// NB: TOML arrays cannot mix strings and tables, so all the commands are tables if one is detailed.
+fn serialize_bump_commands<S>(
+    commands: &[BumpCommand],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if commands
+        .iter()
+        .all(|c| matches!(c, BumpCommand::Command(_)))
+    {
+        commands.serialize(serializer)
+    } else {
+        let configs: Vec<_> = commands.iter().map(BumpCommand::config).collect();
+        configs.serialize(serializer)
+    }
+}
+
+/// Bump command settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BumpCommandConfig {
+    /// Command template (eg. `npm version {{version}}`)
+    pub cmd: String,
+    /// If set, the command is run with `sh -c`, instead of being split into words
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub shell: bool,
+    /// Working directory, relative to the repo root
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
+    /// Timeout, in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    /// Environment variables (templates)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+}
+
 /// Release configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ReleaseConfig {
+    /// Version scheme
+    #[serde(default)]
+    pub scheme: VersionSchemeKind,
+    /// CalVer format (defaults to `YYYY.0M.MICRO`)
+    pub calver_format: Option<String>,
+    /// Tag pattern (defaults to `v{version}`)
+    pub tag_pattern: Option<String>,
+    /// Package name, for the `{package}` placeholder of the tag pattern
+    pub package: Option<String>,
+    /// Versioning policy before 1.0.0
+    #[serde(default)]
+    pub pre_stable: PreStablePolicy,
     /// Commands to execute when the version is bumped
-    pub bump_commands: Vec<String>,
+    #[serde(serialize_with = "serialize_bump_commands")]
+    pub bump_commands: Vec<BumpCommand>,
+    /// Files whose version is bumped natively (eg. `Cargo.toml` or `package.json`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bump_files: Vec<BumpFile>,
+    /// Pre-release channel of each branch (eg. `next = "beta"`)
+    #[serde(default)]
+    pub channels: BTreeMap<String, String>,
+}
+
+/// Package configuration, for monorepos with independently versioned packages
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PackageConfig {
+    /// Package directory, relative to the repo root
+    pub path: PathBuf,
+    /// Tag pattern (defaults to `{package}@{version}`)
+    pub tag_pattern: Option<String>,
+    /// Changelog file, relative to the repo root (defaults to `CHANGELOG.md` in the package directory)
+    pub changelog: Option<PathBuf>,
+    /// Commands to execute when the version is bumped, in the package directory
+    #[serde(default, serialize_with = "serialize_bump_commands")]
+    pub bump_commands: Vec<BumpCommand>,
+    /// Files whose version is bumped natively, relative to the package directory
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bump_files: Vec<BumpFile>,
 }
 
 /// Configuration object
@@ -91,24 +615,53 @@ pub struct Config {
     pub commit: CommitsConfig,
     /// Custom hooks
     pub hooks: BTreeMap<String, Vec<String>>,
+    /// Lint config
+    #[serde(default)]
+    pub lint: LintConfig,
+    /// Gitmoji config
+    #[serde(default)]
+    pub gitmoji: GitmojiConfig,
+    /// Issue references config
+    #[serde(default)]
+    pub issues: IssuesConfig,
     /// Changelog config
     pub changelog: ChangeLogConfig,
     /// Release config
     pub release: ReleaseConfig,
+    /// Packages, released independently (cf. [Config::for_package])
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub packages: BTreeMap<String, PackageConfig>,
 }
 
 impl Config {
     /// Loads the configuration file for the current working directory
+    ///
+    /// If a commitlint config file exists, its rules are applied on top of the configuration
+    /// (cf. [commitlint]).
     pub fn load(repo_path: &Path) -> Result<Option<Self>> {
         let cfg_file = repo_path.join(CONFIG_DIR).join(CONFIG_FILE);
-        if cfg_file.exists() {
+        let commitlint_file = CommitlintConfig::find(repo_path);
+
+        let mut cfg = if cfg_file.exists() {
             let cfg_str = fs::read_to_string(&cfg_file)?;
-            let mut cfg = toml::from_str::<Config>(&cfg_str)?;
-            cfg.root_dir = repo_path.to_path_buf();
-            Ok(Some(cfg))
+            toml::from_str::<Config>(&cfg_str)?
+        } else if commitlint_file.is_some() {
+            Config::default()
         } else {
-            Ok(None)
+            return Ok(None);
+        };
+        cfg.root_dir = repo_path.to_path_buf();
+        cfg.commit.policies.validate()?;
+
+        if let Some(file) = commitlint_file {
+            debug!("Importing commitlint config {}", file.display());
+            let ignored = CommitlintConfig::load(&file)?.apply(&mut cfg);
+            for rule in ignored {
+                debug!("commitlint rule '{rule}' is not supported -> ignored");
+            }
         }
+
+        Ok(Some(cfg))
     }
 
     /// Saves a [Configuration] to the repo
@@ -126,6 +679,20 @@ impl Config {
         self.commit.types.keys().cloned().collect()
     }
 
+    /// Returns the canonical commit type of an alias (or the type itself)
+    ///
+    /// NB: the valid types are not aliased.
+    pub fn canonical_commit_type<'a>(&'a self, commit_type: &'a str) -> &'a str {
+        if self.commit.types.contains_key(commit_type) {
+            return commit_type;
+        }
+        self.commit
+            .aliases
+            .get(commit_type)
+            .map(|t| t.as_str())
+            .unwrap_or(commit_type)
+    }
+
     /// Checks if a commit type causes a minor increment
     pub fn type_is_minor_inc(&self, commit_type: &str) -> bool {
         self.commit
@@ -133,8 +700,117 @@ impl Config {
             .contains(&commit_type.to_string())
     }
 
+    /// Returns the version increment of a commit type, with its scopes
+    ///
+    /// A `type(scope)` rule overrides the `type` rule. For several scopes, the highest increment is returned.
+    pub fn type_bump(&self, commit_type: &str, scopes: &[&str]) -> Bump {
+        self.type_bump_rule(commit_type, scopes).0
+    }
+
+    /// Returns the version increment of a commit type, with its scopes, and the rule which applies
+    /// (eg. `commit.bump.feat`)
+    pub fn type_bump_rule(&self, commit_type: &str, scopes: &[&str]) -> (Bump, String) {
+        let mut rules = Vec::<(Bump, String)>::new();
+        let mut type_rule_applies = scopes.is_empty();
+        for scope in scopes {
+            let key = format!("{commit_type}({scope})");
+            match self.commit.bump.get(&key) {
+                Some(bump) => rules.push((*bump, format!("commit.bump.\"{key}\""))),
+                None => type_rule_applies = true,
+            }
+        }
+        if type_rule_applies {
+            rules.push(match self.commit.bump.get(commit_type) {
+                Some(bump) => (*bump, format!("commit.bump.{commit_type}")),
+                None if self.type_is_minor_inc(commit_type) => {
+                    (Bump::Minor, "commit.types_inc_minor".to_string())
+                }
+                None => (
+                    CommitsConfig::default_type_bump(commit_type),
+                    "default".to_string(),
+                ),
+            });
+        }
+        rules
+            .into_iter()
+            .max_by_key(|(bump, _)| *bump)
+            .expect("at least 1 rule applies")
+    }
+
     /// Returns the folder for hook
     pub fn hooks_folder(&self) -> PathBuf {
         self.root_dir.join(CONFIG_DIR).join("hooks")
     }
+
+    /// Returns the configuration of a package (cf. `[packages.<name>]`)
+    ///
+    /// The release settings of the package replace the settings of the repo.
+    /// The bump files and commands are relative to the package directory.
+    pub fn for_package(&self, name: &str) -> Result<Config> {
+        let package = self
+            .packages
+            .get(name)
+            .ok_or_else(|| Error::InternalError(format!("Unknown package '{name}'")))?;
+
+        let mut config = self.clone();
+        config.release.package = Some(name.to_string());
+        config.release.tag_pattern = Some(
+            package
+                .tag_pattern
+                .clone()
+                .unwrap_or_else(|| DEFAULT_PACKAGE_TAG_PATTERN.to_string()),
+        );
+        config.release.bump_files = package
+            .bump_files
+            .iter()
+            .map(|f| BumpFile {
+                path: package.path.join(&f.path),
+                ..f.clone()
+            })
+            .collect();
+        config.release.bump_commands = package
+            .bump_commands
+            .iter()
+            .map(|c| {
+                let mut settings = c.config();
+                settings.cwd = Some(match settings.cwd {
+                    Some(cwd) => package.path.join(cwd),
+                    None => package.path.clone(),
+                });
+                BumpCommand::Detailed(settings)
+            })
+            .collect();
+        config.changelog.file = Some(
+            package
+                .changelog
+                .clone()
+                .unwrap_or_else(|| package.path.join(CHANGELOG_FILE)),
+        );
+        Ok(config)
+    }
+
+    /// Returns the package of a package configuration (cf. [Config::for_package])
+    pub fn current_package(&self) -> Option<(&str, &PackageConfig)> {
+        let name = self.release.package.as_deref()?;
+        self.packages
+            .get_key_value(name)
+            .map(|(name, package)| (name.as_str(), package))
+    }
+
+    /// Returns the configurations to release: the selected packages, all the packages, or the repo
+    pub fn release_configs(&self, packages: &[String], all_packages: bool) -> Result<Vec<Config>> {
+        if all_packages {
+            if self.packages.is_empty() {
+                return Err(Error::InternalError("No package is configured".to_string()));
+            }
+            self.packages
+                .keys()
+                .map(|name| self.for_package(name))
+                .collect()
+        } else if !packages.is_empty() {
+            packages.iter().map(|name| self.for_package(name)).collect()
+        } else {
+            Ok(vec![self.clone()])
+        }
+    }
 }
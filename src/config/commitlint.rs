@@ -0,0 +1,301 @@
+//! Import of commitlint configuration files
+//!
+//! The JSON and YAML variants are supported (`.commitlintrc`, `.commitlintrc.json`,
+//! `.commitlintrc.yaml` and `.commitlintrc.yml`), as well as the `@commitlint/config-conventional` preset.
+//!
+//! ## Supported rules
+//!
+//! | commitlint rule        | gitext rule             | notes                                           |
+//! |------------------------|-------------------------|-------------------------------------------------|
+//! | `type-enum`            | commit types            | `always` only                                   |
+//! | `type-case`            | `type_case`             | `always` + `lower-case` only                    |
+//! | `scope-enum`           | `scope_enum`            | `always` only                                   |
+//! | `scope-case`           | `scope_case`            |                                                 |
+//! | `scope-empty`          | `scope_required`        | `never` only                                    |
+//! | `subject-case`         | `subject_case`          | `never` is approximated as `lower-first`        |
+//! | `subject-full-stop`    | `subject_full_stop`     | `never` + `.` only                              |
+//! | `header-max-length`    | `header_max_length`     |                                                 |
+//! | `body-max-line-length` | `body_max_line_length`  | also applies to the footer lines                |
+//! | `body-leading-blank`   | `body_leading_blank`    | `always` only                                   |
+//! | `body-empty`           | `body_required_types`   | `never` only, applies to all types              |
+//! | `footer-empty`         | `footer_required_types` | `never` only, applies to all types              |
+//!
+//! `type-empty` and `subject-empty` are always enforced. Other rules are ignored.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use indoc::indoc;
+use serde::Deserialize;
+use serde_yaml::Value;
+
+use crate::{
+    config::{CommitsConfig, Config},
+    error::{Error, Result},
+    lint::{Case, LintLevel, LintRule},
+};
+
+/// Supported commitlint configuration files, by order of precedence
+pub const COMMITLINT_FILES: [&str; 4] = [
+    ".commitlintrc",
+    ".commitlintrc.json",
+    ".commitlintrc.yaml",
+    ".commitlintrc.yml",
+];
+
+/// Unsupported commitlint configuration files
+const COMMITLINT_JS_FILES: [&str; 3] = [
+    "commitlint.config.js",
+    "commitlint.config.cjs",
+    ".commitlintrc.js",
+];
+
+/// Rules of the `@commitlint/config-conventional` preset
+const CONFIG_CONVENTIONAL: &str = indoc!(
+    r#"
+    rules:
+      body-leading-blank: [1, always]
+      body-max-line-length: [2, always, 100]
+      footer-leading-blank: [1, always]
+      footer-max-line-length: [2, always, 100]
+      header-max-length: [2, always, 100]
+      subject-case: [2, never, [sentence-case, start-case, pascal-case, upper-case]]
+      subject-empty: [2, never]
+      subject-full-stop: [2, never, "."]
+      type-case: [2, always, lower-case]
+      type-empty: [2, never]
+      type-enum: [2, always, [build, chore, ci, docs, feat, fix, perf, refactor, revert, style, test]]
+    "#
+);
+
+/// Rules which are always enforced by gitext
+const ENFORCED_RULES: [&str; 2] = ["type-empty", "subject-empty"];
+
+/// One or several values
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Default for OneOrMany {
+    fn default() -> Self {
+        OneOrMany::Many(vec![])
+    }
+}
+
+/// commitlint configuration file
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CommitlintConfig {
+    /// Shared configurations
+    #[serde(default)]
+    extends: OneOrMany,
+    /// Rules (`[level, applicable, value]`)
+    #[serde(default)]
+    rules: BTreeMap<String, Vec<Value>>,
+}
+
+impl CommitlintConfig {
+    /// Returns the commitlint config file in a directory, if any
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        for file in COMMITLINT_JS_FILES {
+            if dir.join(file).exists() {
+                log::warn!("{file} is not supported, use a JSON or YAML commitlint config file");
+            }
+        }
+
+        COMMITLINT_FILES
+            .iter()
+            .map(|f| dir.join(f))
+            .find(|f| f.exists())
+    }
+
+    /// Loads a commitlint config file
+    pub fn load(file: &Path) -> Result<Self> {
+        let s = fs::read_to_string(file)?;
+        Self::parse(&s)
+    }
+
+    /// Parses a commitlint config (JSON or YAML)
+    pub fn parse(s: &str) -> Result<Self> {
+        // NB: YAML is a superset of JSON
+        serde_yaml::from_str(s)
+            .map_err(|err| Error::InternalError(format!("Invalid commitlint config: {err}")))
+    }
+
+    /// Returns the shared configurations
+    pub fn extends(&self) -> Vec<String> {
+        match &self.extends {
+            OneOrMany::One(s) => vec![s.clone()],
+            OneOrMany::Many(v) => v.clone(),
+        }
+    }
+
+    /// Returns the rules, including the rules from the supported shared configurations
+    pub fn resolved_rules(&self) -> BTreeMap<String, Vec<Value>> {
+        let mut rules = BTreeMap::new();
+        for extend in self.extends() {
+            if extend == "@commitlint/config-conventional" {
+                let preset = Self::parse(CONFIG_CONVENTIONAL).expect("Invalid preset");
+                rules.extend(preset.rules);
+            } else {
+                log::warn!("commitlint shared config '{extend}' is not supported");
+            }
+        }
+        rules.extend(self.rules.clone());
+        rules
+    }
+
+    /// Applies the commitlint rules to a config
+    ///
+    /// Returns the rules which are not supported and have been ignored.
+    pub fn apply(&self, config: &mut Config) -> Vec<String> {
+        let mut ignored = vec![];
+        // NB: the commit types are set first, as other rules depend on them
+        let mut rules: Vec<_> = self.resolved_rules().into_iter().collect();
+        rules.sort_by_key(|(name, _)| name != "type-enum");
+        for (name, rule) in rules {
+            let rule = CommitlintRule::from_values(&rule);
+            if !rule.apply(&name, config) {
+                ignored.push(name);
+            }
+        }
+        ignored
+    }
+}
+
+/// commitlint rule
+struct CommitlintRule<'a> {
+    level: LintLevel,
+    always: bool,
+    value: Option<&'a Value>,
+}
+
+impl<'a> CommitlintRule<'a> {
+    /// Parses a `[level, applicable, value]` rule
+    fn from_values(values: &'a [Value]) -> Self {
+        let level = match values.first().and_then(|v| v.as_u64()) {
+            Some(1) => LintLevel::Warn,
+            Some(2) => LintLevel::Error,
+            _ => LintLevel::Off,
+        };
+        let always = values.get(1).and_then(|v| v.as_str()) != Some("never");
+        Self {
+            level,
+            always,
+            value: values.get(2),
+        }
+    }
+
+    /// Returns the value as a list of strings
+    fn strings(&self) -> Vec<String> {
+        match self.value {
+            Some(Value::String(s)) => vec![s.clone()],
+            Some(Value::Sequence(seq)) => seq
+                .iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Returns the value as a number
+    fn number(&self) -> Option<usize> {
+        self.value.and_then(|v| v.as_u64()).map(|n| n as usize)
+    }
+
+    /// Returns the case
+    ///
+    /// NB: `never` cases are approximated as `lower-first`.
+    fn case(&self) -> Option<Case> {
+        if !self.always {
+            return Some(Case::LowerFirst);
+        }
+        match self.strings().first().map(|s| s.as_str()) {
+            Some("lower-case") => Some(Case::LowerCase),
+            Some("upper-case") => Some(Case::UpperCase),
+            Some("sentence-case") => Some(Case::SentenceCase),
+            _ => None,
+        }
+    }
+
+    /// Applies the rule to a config, and returns `false` if the rule is not supported
+    fn apply(&self, name: &str, config: &mut Config) -> bool {
+        let lint = &mut config.lint;
+        match (name, self.always) {
+            (name, _) if ENFORCED_RULES.contains(&name) => {}
+            ("type-enum", true) => {
+                let default_types = CommitsConfig::default().types;
+                let types = self.strings();
+                if self.level == LintLevel::Off || types.is_empty() {
+                    return true;
+                }
+                config.commit.types = types
+                    .iter()
+                    .map(|t| {
+                        let desc = config
+                            .commit
+                            .types
+                            .get(t)
+                            .or_else(|| default_types.get(t))
+                            .cloned()
+                            .unwrap_or_else(|| t.clone());
+                        (t.clone(), desc)
+                    })
+                    .collect();
+                config.changelog.types.retain(|t| types.contains(t));
+            }
+            ("type-case", true) if self.case() == Some(Case::LowerCase) => {
+                lint.type_case = self.level;
+            }
+            ("scope-enum", true) => {
+                let scopes = self.strings();
+                let level = if scopes.is_empty() {
+                    LintLevel::Off
+                } else {
+                    self.level
+                };
+                lint.scope_enum = LintRule::new(level, scopes);
+            }
+            ("scope-case", _) => match self.case() {
+                Some(case) => lint.scope_case = LintRule::new(self.level, case),
+                None => return false,
+            },
+            ("scope-empty", false) => {
+                lint.scope_required = self.level;
+            }
+            ("subject-case", _) => match self.case() {
+                Some(case) => lint.subject_case = LintRule::new(self.level, case),
+                None => return false,
+            },
+            ("subject-full-stop", false) if self.strings() == vec!["."] => {
+                lint.subject_full_stop = self.level;
+            }
+            ("header-max-length", true) => match self.number() {
+                Some(n) => lint.header_max_length = LintRule::new(self.level, n),
+                None => return false,
+            },
+            ("body-max-line-length", true) => match self.number() {
+                Some(n) => lint.body_max_line_length = LintRule::new(self.level, n),
+                None => return false,
+            },
+            ("body-leading-blank", true) => {
+                lint.body_leading_blank = self.level;
+            }
+            ("body-empty", false) => {
+                lint.body_required_types =
+                    LintRule::new(self.level, config.commit.types.keys().cloned().collect());
+            }
+            ("footer-empty", false) => {
+                lint.footer_required_types =
+                    LintRule::new(self.level, config.commit.types.keys().cloned().collect());
+            }
+            _ => return false,
+        }
+        true
+    }
+}
@@ -1,16 +1,32 @@
 //! Conventional commit parsing and formatting
 
-use std::{io::BufRead, string::ToString};
+pub mod issue;
+pub mod special;
 
+use std::{collections::BTreeMap, fmt::Display};
+
+use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
 
 use crate::{
+    config::{Config, IssuesConfig, LintConfig},
     error::{Error, Result},
+    git::GitCommit,
+    gitmoji::{Gitmoji, GitmojiPosition},
+    lint::{apply_fixes, check_rules, Diagnostic, Span},
     utils::StringExt,
 };
 
+use issue::IssueRef;
+use special::SpecialCommit;
+
 /// Conventional commit message.
-#[derive(Debug, PartialEq, Default)]
+///
+/// NB: the serialized message also contains the list of scopes, issue references and trailers
+/// (cf. [ConventionalCommitMessage::scopes], [ConventionalCommitMessage::issues] and [ConventionalCommitMessage::trailers]).
+#[derive(Debug, PartialEq, Default, Deserialize)]
+#[serde(default)]
 pub struct ConventionalCommitMessage {
     /// Commit type
     pub r#type: String,
@@ -21,289 +37,963 @@ pub struct ConventionalCommitMessage {
     /// Commit body
     pub body: Option<String>,
     /// Breaking change
+    ///
+    /// The description is empty if the breaking change is only indicated by the `!` marker.
     pub breaking_change: Option<String>,
-    /// Closed issues
-    pub closed_issues: Option<Vec<u32>>,
+    /// Footer items (breaking change, issue references and other trailers), in order of appearance
+    pub footer: Vec<FooterItem>,
+    /// Gitmoji
+    pub gitmoji: Option<Gitmoji>,
+}
+
+/// Footer item (cf. [ConventionalCommitMessage::footer])
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FooterItem {
+    /// Breaking change trailer, with its token (`BREAKING CHANGE` or `BREAKING-CHANGE`)
+    ///
+    /// NB: the description is the [ConventionalCommitMessage::breaking_change].
+    BreakingChange(String),
+    /// Issue references of a trailer (eg. `Closes #1, #2`)
+    Issues(Vec<IssueRef>),
+    /// Other trailer (eg. `Signed-off-by: John Doe`)
+    Trailer(Trailer),
+}
+
+/// Separator between a trailer token and its value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrailerSeparator {
+    /// `token: value`
+    Colon,
+    /// `token #value`
+    Hash,
+}
+
+impl Display for TrailerSeparator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrailerSeparator::Colon => write!(f, ": "),
+            TrailerSeparator::Hash => write!(f, " #"),
+        }
+    }
+}
+
+/// Commit message trailer (aka footer)
+///
+/// A trailer is a `token: value` or `token #value` line in the last paragraph(s) of the message.
+/// The value may span several lines, continuation lines starting with whitespace
+/// (cf. [git-interpret-trailers](https://git-scm.com/docs/git-interpret-trailers)).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trailer {
+    /// Trailer token (eg. `Reviewed-by`)
+    pub token: String,
+    /// Separator
+    pub separator: TrailerSeparator,
+    /// Trailer value, including the continuation lines
+    pub value: String,
+}
+
+impl Trailer {
+    /// Creates a new `token: value` trailer
+    pub fn new(token: &str, value: &str) -> Self {
+        Self {
+            token: token.to_string(),
+            separator: TrailerSeparator::Colon,
+            value: value.to_string(),
+        }
+    }
+
+    /// Checks if the trailer token matches (case insensitive)
+    pub fn is(&self, token: &str) -> bool {
+        self.token.eq_ignore_ascii_case(token)
+    }
+
+    /// Returns the value with the continuation lines unfolded into a single line
+    pub fn unfolded_value(&self) -> String {
+        self.value
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns the regex of a trailer line (cf. [Trailer::parse_line])
+    fn regex() -> Regex {
+        Regex::new(
+            r"^(?P<token>BREAKING CHANGE|[A-Za-z0-9][A-Za-z0-9-]*)(?P<sep>: | #)(?P<value>.*)$",
+        )
+        .expect("Invalid regex")
+    }
+
+    /// Parses a trailer line (without continuation lines)
+    fn parse_line(line: &str, regex_trailer: &Regex) -> Option<Self> {
+        let capts = regex_trailer.captures(line)?;
+        let separator = match &capts["sep"] {
+            " #" => TrailerSeparator::Hash,
+            _ => TrailerSeparator::Colon,
+        };
+        Some(Self {
+            token: capts["token"].to_string(),
+            separator,
+            value: capts["value"].trim_end().to_string(),
+        })
+    }
+
+    /// Checks if the token is a breaking change token (`BREAKING CHANGE` or `BREAKING-CHANGE`)
+    fn is_breaking_change(&self) -> bool {
+        self.token == BREAKING_CHANGE_TOKEN || self.token == "BREAKING-CHANGE"
+    }
 }
 
-impl ToString for ConventionalCommitMessage {
-    fn to_string(&self) -> String {
-        let mut s = String::new();
+impl Display for Trailer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.token, self.separator, self.value)
+    }
+}
+
+impl Serialize for ConventionalCommitMessage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("ConventionalCommitMessage", 10)?;
+        s.serialize_field("type", &self.r#type)?;
+        s.serialize_field("scope", &self.scope)?;
+        s.serialize_field("scopes", &self.scopes())?;
+        s.serialize_field("subject", &self.subject)?;
+        s.serialize_field("body", &self.body)?;
+        s.serialize_field("breaking_change", &self.breaking_change)?;
+        s.serialize_field("issues", &self.issues())?;
+        s.serialize_field("trailers", &self.trailers())?;
+        s.serialize_field("footer", &self.footer)?;
+        s.serialize_field("gitmoji", &self.gitmoji)?;
+        s.end()
+    }
+}
 
+impl Display for ConventionalCommitMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // prefix
-        s.push_str(
-            format!(
-                "{}{}{}: {}",
-                self.r#type,
-                self.scope
-                    .as_ref()
-                    .map(|s| format!("({s})"))
-                    .unwrap_or_default(),
-                self.breaking_change
-                    .as_ref()
-                    .map(|_| "!")
-                    .unwrap_or_default(),
-                self.subject
-            )
-            .as_str(),
-        );
+        let (gitmoji_type, gitmoji_subject) = match &self.gitmoji {
+            Some(g) if g.position == GitmojiPosition::BeforeType => {
+                (format!("{g} "), "".to_string())
+            }
+            Some(g) => ("".to_string(), format!("{g} ")),
+            None => ("".to_string(), "".to_string()),
+        };
+        write!(
+            f,
+            "{}{}{}{}: {}{}",
+            gitmoji_type,
+            self.r#type,
+            self.scope
+                .as_ref()
+                .map(|s| format!("({s})"))
+                .unwrap_or_default(),
+            self.breaking_change
+                .as_ref()
+                .map(|_| "!")
+                .unwrap_or_default(),
+            gitmoji_subject,
+            self.subject
+        )?;
 
         // body
         if let Some(b) = &self.body {
-            s.push_str("\n\n");
-            s.push_str(b.as_str());
-        }
-
-        // breaking change
-        let mut has_breaking_change = false;
-        if let Some(b) = &self.breaking_change {
-            has_breaking_change = true;
-            s.push_str("\n\n");
-            s.push_str(format!("BREAKING CHANGE: {}", b).as_str());
+            write!(f, "\n\n{b}")?;
         }
 
-        // closed issues
-        if let Some(issues) = &self.closed_issues {
-            if !issues.is_empty() {
-                if !has_breaking_change {
-                    s.push('\n');
+        // footer
+        // NB: an empty description is only indicated by the `!` marker
+        let mut breaking_change = self.breaking_change.as_deref().filter(|b| !b.is_empty());
+        let mut footer = Vec::<String>::new();
+        for item in &self.footer {
+            match item {
+                FooterItem::BreakingChange(token) => {
+                    if let Some(b) = breaking_change.take() {
+                        footer.push(format!("{token}: {b}"));
+                    }
                 }
-                for issue in issues {
-                    s.push('\n');
-                    s.push_str(format!("Closes #{issue}").as_str());
+                FooterItem::Issues(refs) => {
+                    if let Some((first, others)) = refs.split_first() {
+                        let mut line = first.to_trailer().to_string();
+                        for issue in others {
+                            line.push_str(&format!(", {issue}"));
+                        }
+                        footer.push(line);
+                    }
                 }
+                FooterItem::Trailer(trailer) => footer.push(trailer.to_string()),
             }
         }
+        // NB: the description may be set without footer item (eg. in `git-c`)
+        if let Some(b) = breaking_change {
+            footer.insert(0, format!("{BREAKING_CHANGE_TOKEN}: {b}"));
+        }
+        if !footer.is_empty() {
+            write!(f, "\n\n{}", footer.join("\n"))?;
+        }
 
-        s
+        Ok(())
     }
 }
 
+/// Checks if a character is allowed in a scope
+fn is_scope_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || ['_', '-', '/', '.'].contains(&c) || c.is_whitespace()
+}
+
+/// Type of the revert commits
+const REVERT_TYPE: &str = "revert";
+
+/// Regex of the header prefix (eg. `feat(api)!`)
+///
+/// cf. https://2fd.github.io/rust-regex-playground
+static REGEX_HEADER_PREFIX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?P<type>[[:word:]]*)(\((?P<scope>[^()]*)\))?(?P<breaking>!?)\s*$")
+        .expect("Invalid regex")
+});
+
+/// Regex of a commit id (full or abbreviated)
+static REGEX_COMMIT_ID: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[0-9a-fA-F]{7,40}$").expect("Invalid regex"));
+
+/// Token of the breaking change trailer
+const BREAKING_CHANGE_TOKEN: &str = "BREAKING CHANGE";
+
+/// Maximum number of passes when fixing a commit message
+const MAX_FIX_PASSES: usize = 5;
+
+/// Maximum edit distance to suggest a valid commit type
+const MAX_TYPE_EDIT_DISTANCE: usize = 3;
+
+/// Parsed commit header (1st line)
+struct CommitHeader {
+    r#type: String,
+    scope: Option<String>,
+    subject: String,
+    breaking_change: Option<String>,
+    gitmoji: Option<Gitmoji>,
+}
+
+/// Parsing options
+struct ParseOptions<'a> {
+    /// Valid commit types
+    valid_types: &'a [String],
+    /// Commit type aliases
+    aliases: &'a BTreeMap<String, String>,
+    /// Lint rules
+    rules: &'a LintConfig,
+    /// Gitmoji mode
+    gitmoji: bool,
+    /// Issue references config
+    issues: &'a IssuesConfig,
+}
+
 impl ConventionalCommitMessage {
     /// Parses a string into a conventional commit message
+    ///
+    /// The first lint error is returned as an error.
+    ///
+    /// NB: the default lint rules are applied, and the repo config (`[lint]` rules, aliases, gitmoji)
+    /// is ignored, so the result may differ from `git-lint` (cf. [Self::parse_with_config]).
     pub fn parse(s: &str, valid_types: &[String]) -> Result<Self> {
-        let mut r#type = String::new();
-        let mut scope: Option<String> = None;
-        let mut subject = String::new();
-        let mut body: Option<String> = None;
-        let mut breaking_change: Option<String> = None;
-        let mut closed_issues: Option<Vec<u32>> = None;
-
-        // cf. https://2fd.github.io/rust-regex-playground
-        let regex_prefix =
-            Regex::new(r"(?P<type>[[:word:]]+)(?P<scope>(\([0-9A-Za-z_\s]+\))?)(?P<breaking>!?)")
-                .expect("Invalid regex");
-
-        #[derive(Debug, PartialEq)]
-        enum Section {
-            Subject,
-            Body,
-            FooterBreakingChange,
-            FooterCloseIssue,
+        let (commit, diagnostics) = Self::parse_with_diagnostics(s, valid_types);
+        Self::first_error(commit, diagnostics)
+    }
+
+    /// Parses a string into a conventional commit message, with the repo configuration
+    ///
+    /// The first lint error is returned as an error.
+    pub fn parse_with_config(s: &str, config: &Config) -> Result<Self> {
+        let (commit, diagnostics) = Self::lint(s, config);
+        Self::first_error(commit, diagnostics)
+    }
+
+    /// Parses a commit message from the history, with the repo configuration
+    ///
+    /// Merge and autosquash commits are handled according to the configured policies,
+    /// and `None` is returned if the commit is skipped.
+    ///
+    /// NB: only the structure of the message is checked (type, scope and breaking change),
+    /// the `[lint]` rules are not applied, so that enabling a rule does not drop older commits.
+    pub fn parse_log_message(s: &str, config: &Config) -> Result<Option<Self>> {
+        Self::parse_history(s, SpecialCommit::detect(s), config)
+    }
+
+    /// Parses a commit of the history, with the repo configuration
+    ///
+    /// Same as [Self::parse_log_message], but the commits with several parents are merge commits,
+    /// whatever their message.
+    pub fn parse_log_commit(commit: &GitCommit, config: &Config) -> Result<Option<Self>> {
+        Self::parse_history(
+            &commit.message,
+            SpecialCommit::detect_commit(commit),
+            config,
+        )
+    }
+
+    /// Parses a commit message from the history, handling the merge and autosquash commits
+    fn parse_history(
+        s: &str,
+        special: Option<SpecialCommit>,
+        config: &Config,
+    ) -> Result<Option<Self>> {
+        let msg = match special {
+            Some(special) => match special.resolve(&config.commit.policies) {
+                Some(msg) => msg,
+                None => return Ok(None),
+            },
+            None => s,
+        };
+        let valid_types = config.valid_commit_types();
+        let options = ParseOptions {
+            valid_types: &valid_types,
+            aliases: &config.commit.aliases,
+            rules: &LintConfig::disabled(),
+            gitmoji: config.gitmoji.enabled,
+            issues: &config.issues,
+        };
+        let (commit, diagnostics) = Self::parse_with_options(msg, &options);
+        Self::first_error(commit, diagnostics).map(Some)
+    }
+
+    /// Lints a commit message with the repo configuration
+    ///
+    /// The commit message is returned as long as its header can be parsed, even if there are errors.
+    ///
+    /// NB: merge and autosquash commits are not linted.
+    pub fn lint(s: &str, config: &Config) -> (Option<Self>, Vec<Diagnostic>) {
+        if SpecialCommit::detect(s).is_some() {
+            return (None, vec![]);
         }
-        let mut prev_section = Section::Subject;
-
-        for (i, line_res) in s.as_bytes().lines().enumerate() {
-            let line = line_res?;
-
-            // >> 1st line
-            if i == 0 {
-                // eprintln!("|> subject line");
-                let parts: Vec<_> = line.splitn(2, ':').collect();
-                if parts.len() != 2 {
-                    return Err(Error::InvalidCommit(
-                        "Conventional commit missing ':' separator".to_string(),
-                    ));
-                }
-                // parse the prefix
-                let prefix = parts[0].trim().to_string();
-                if let Some(capts) = regex_prefix.captures(&prefix) {
-                    // get type
-                    r#type = match capts.name("type") {
-                        Some(m) => {
-                            let t = m.as_str();
-                            if !valid_types.contains(&t.to_string()) {
-                                return Err(Error::InvalidCommit(format!(
-                                    "Invalid conventional commit type '{}'",
-                                    m.as_str()
-                                )));
-                            }
-                            t.to_string()
-                        }
-                        None => {
-                            return Err(Error::InvalidCommit(format!(
-                                "Missing conventional commit type in '{}'",
-                                line
-                            )));
-                        }
-                    };
-                    // get scope
-                    scope = match capts.name("scope") {
-                        Some(m) => {
-                            let s = m.as_str().trim().to_string();
-                            if s.is_empty() {
-                                None
-                            } else {
-                                let s = s.trim_matches('(').trim_matches(')');
-                                // check lowercase
-                                if !s.starts_with_lowercase() {
-                                    return Err(Error::InvalidCommit(format!(
-                                        "Invalid commit: scope ({s}) must start with lowercase"
-                                    )));
-                                }
-                                Some(s.to_string())
-                            }
-                        }
-                        None => None,
-                    };
-                    // get breaking change indicator
-                    breaking_change = match capts.name("breaking") {
-                        Some(m) => {
-                            let s = m.as_str().trim().to_string();
-                            if s.is_empty() {
-                                None
-                            } else {
-                                Some("".to_string())
-                            }
-                        }
-                        None => None,
-                    };
-                    // eprintln!("type: {:?}", r#type);
-                    // eprintln!("scope: {:?}", scope);
-                    // eprintln!("desc: {:?}", description);
-                } else {
-                    return Err(Error::InvalidCommit(format!("Invalid commit: {}", line)));
-                }
+        let valid_types = config.valid_commit_types();
+        let options = ParseOptions {
+            valid_types: &valid_types,
+            aliases: &config.commit.aliases,
+            rules: &config.lint,
+            gitmoji: config.gitmoji.enabled,
+            issues: &config.issues,
+        };
+        Self::parse_with_options(s, &options)
+    }
 
-                // process subject
-                subject = parts[1].trim().to_string();
-                if !subject.starts_with_lowercase() {
-                    return Err(Error::InvalidCommit(
-                        "Invalid commit: subject must start with lowercase".to_string(),
-                    ));
-                }
+    /// Applies the mechanical fixes to a commit message, with the repo configuration
+    ///
+    /// The message is linted again after each pass, until there is nothing left to fix.
+    pub fn fix(s: &str, config: &Config) -> String {
+        let mut fixed = s.to_string();
+        for _ in 0..MAX_FIX_PASSES {
+            let (_, diagnostics) = Self::lint(&fixed, config);
+            let next = apply_fixes(&fixed, &diagnostics);
+            if next == fixed {
+                break;
+            }
+            fixed = next;
+        }
+        fixed
+    }
 
-                continue;
+    /// Parses a string into a conventional commit message, and collects all the lint diagnostics
+    ///
+    /// The default lint rules are applied (cf. [LintConfig]), and the repo config is ignored:
+    /// use [Self::lint] to get the same diagnostics as `git-lint`.
+    pub fn parse_with_diagnostics(
+        s: &str,
+        valid_types: &[String],
+    ) -> (Option<Self>, Vec<Diagnostic>) {
+        Self::parse_with_rules(s, valid_types, &LintConfig::default())
+    }
+
+    /// Parses a string into a conventional commit message, and collects all the lint diagnostics
+    /// for the given rules
+    ///
+    /// The commit message is returned as long as its header can be parsed, even if there are errors.
+    pub fn parse_with_rules(
+        s: &str,
+        valid_types: &[String],
+        rules: &LintConfig,
+    ) -> (Option<Self>, Vec<Diagnostic>) {
+        let issues = IssuesConfig::default();
+        let options = ParseOptions {
+            valid_types,
+            aliases: &BTreeMap::new(),
+            rules,
+            gitmoji: false,
+            issues: &issues,
+        };
+        Self::parse_with_options(s, &options)
+    }
+
+    /// Returns the first error diagnostic as an error, or the commit
+    fn first_error(commit: Option<Self>, diagnostics: Vec<Diagnostic>) -> Result<Self> {
+        if let Some(d) = diagnostics.iter().find(|d| d.is_error()) {
+            return Err(Error::InvalidCommit(format!(
+                "Invalid commit: {}",
+                d.message
+            )));
+        }
+        commit.ok_or_else(|| Error::InvalidCommit("Invalid commit".to_string()))
+    }
+
+    /// Parses a string into a conventional commit message, and collects all the lint diagnostics
+    fn parse_with_options(s: &str, options: &ParseOptions) -> (Option<Self>, Vec<Diagnostic>) {
+        let rules = options.rules;
+        let mut diagnostics = Vec::<Diagnostic>::new();
+        let lines: Vec<&str> = s.lines().collect();
+
+        // >> 1st line
+        let header = Self::parse_header(
+            lines.first().copied().unwrap_or_default(),
+            options,
+            &mut diagnostics,
+        );
+
+        // line after subject
+        // NB: if missing, the body starts right after the subject
+        let mut rest_start = 2;
+        if let Some(line) = lines.get(1) {
+            if !line.is_empty() {
+                diagnostics.extend(
+                    rules
+                        .body_leading_blank
+                        .diagnostic(
+                            "body-leading-blank",
+                            "body must be separated by an empty line",
+                            Span::new(2, 1, 0),
+                        )
+                        .map(|d| {
+                            d.with_suggestion("insert an empty line after the subject", Some("\n"))
+                        }),
+                );
+                rest_start = 1;
             }
+        }
 
-            // line after subject
-            if prev_section == Section::Subject {
-                // eprintln!("|> after subject line");
-                if !line.is_empty() {
-                    return Err(Error::InvalidCommit(
-                        "Invalid commit: body must be separated by an empty line".to_string(),
-                    ));
-                } else {
-                    prev_section = Section::Body;
-                    continue;
-                }
+        // body and footer (trailing empty lines are ignored)
+        let mut rest: &[&str] = lines.get(rest_start..).unwrap_or_default();
+        while let Some((last, others)) = rest.split_last() {
+            if !last.trim().is_empty() {
+                break;
             }
+            rest = others;
+        }
 
-            // new breaking change line
-            if prev_section == Section::Body && line.starts_with("BREAKING CHANGE:") {
-                // eprintln!("|> new breaking change line");
-                // next line in body is BREAKING CHANGE
-                // NB: strip newline on the previous line
-                if let Some(b) = &mut body {
-                    if let Some(body_stripped) = b.strip_suffix('\n') {
-                        body = Some(body_stripped.to_string());
-                    } else {
-                        return Err(Error::InvalidCommit(
-                            "Invalid commit: BREAKING CHANGE must be preceded from the body by an empty line".to_string(),
-                        ));
-                    }
-                }
-                if breaking_change.is_some() {
-                    return Err(Error::InvalidCommit(
-                        "Invalid commit: Several breaking changes".to_string(),
+        let regex_trailer = Trailer::regex();
+        let footer_start = Self::footer_start(rest, &regex_trailer);
+        let body_lines = &rest[..footer_start];
+        let body = if body_lines.is_empty() {
+            None
+        } else {
+            Some(body_lines.join("\n").trim_end().to_string())
+        };
+
+        let mut breaking_change = header.as_ref().and_then(|h| h.breaking_change.clone());
+        let key_patterns = options.issues.key_regexes();
+        let mut footer = Vec::<FooterItem>::new();
+        let mut has_breaking_change_footer = false;
+        for (i, trailer) in Self::parse_trailers(&rest[footer_start..], &regex_trailer) {
+            let line_idx = rest_start + footer_start + i;
+            let line_span = Span::line(line_idx + 1, lines[line_idx]);
+            if trailer.is_breaking_change() {
+                // NB: the footer description is merged with the `!` marker
+                if has_breaking_change_footer {
+                    diagnostics.push(Diagnostic::error(
+                        "footer-breaking-change",
+                        "several breaking changes",
+                        line_span,
                     ));
-                } else {
-                    breaking_change = Some(
-                        line.trim_start_matches("BREAKING CHANGE:")
-                            .trim()
-                            .to_string(),
-                    );
+                    continue;
                 }
-                prev_section = Section::FooterBreakingChange;
-                continue;
-            }
-
-            // new closed issue line
-            if (prev_section == Section::Body
-                || prev_section == Section::FooterBreakingChange
-                || prev_section == Section::FooterCloseIssue)
-                && line.starts_with("Closes #")
-            {
-                // eprintln!("|> new closed issue line");
-                // NB: strip newline on the previous line if the issue is after the body
-                if prev_section == Section::Body {
-                    if let Some(b) = &mut body {
-                        if let Some(body_stripped) = b.strip_suffix('\n') {
-                            body = Some(body_stripped.to_string());
-                        } else {
-                            return Err(Error::InvalidCommit(
-                                "Invalid commit: Closes must be preceded from the body by an empty line".to_string(),
-                            ));
+                has_breaking_change_footer = true;
+                breaking_change = Some(trailer.value);
+                footer.push(FooterItem::BreakingChange(trailer.token));
+            } else if let Some((action, refs)) = IssueRef::from_trailer(&trailer, &key_patterns) {
+                match refs {
+                    Some(refs) => footer.push(FooterItem::Issues(refs)),
+                    // NB: the values which are not issue references are kept as trailers
+                    // (eg. `Refs: <commit id>` or `Fixes: abc1234 ("subject")`)
+                    None => {
+                        if action.closes() {
+                            diagnostics.extend(
+                                rules
+                                    .footer_issue
+                                    .diagnostic(
+                                        "footer-issue",
+                                        &format!("invalid issue reference '{}'", trailer.value),
+                                        line_span,
+                                    )
+                                    .map(|d| {
+                                        d.with_suggestion(
+                                            "use '#12', '!45', 'org/repo#12' or a tracker key",
+                                            None,
+                                        )
+                                    }),
+                            );
                         }
+                        footer.push(FooterItem::Trailer(trailer));
                     }
                 }
-                let issue_str = line.trim_start_matches("Closes #");
-                let issue_nb = match issue_str.parse::<u32>() {
-                    Ok(id) => id,
-                    Err(_) => {
-                        return Err(Error::InvalidCommit(
-                            "Invalid commit: invalid issue number".to_string(),
-                        ));
-                    }
+            } else {
+                footer.push(FooterItem::Trailer(trailer));
+            }
+        }
+
+        let commit = header.map(|h| ConventionalCommitMessage {
+            r#type: h.r#type,
+            scope: h.scope,
+            subject: h.subject,
+            body,
+            breaking_change,
+            footer,
+            gitmoji: h.gitmoji,
+        });
+        if let Some(c) = &commit {
+            check_rules(c, &lines, rules, &mut diagnostics);
+        }
+        (commit, diagnostics)
+    }
+
+    /// Returns the scopes (eg. `ui` and `api` for `feat(ui,api): ...`)
+    pub fn scopes(&self) -> Vec<&str> {
+        self.scope
+            .as_deref()
+            .map(|s| {
+                s.split(',')
+                    .map(|s| s.trim())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Checks if the commit introduces a breaking change
+    pub fn is_breaking(&self) -> bool {
+        self.breaking_change.is_some()
+    }
+
+    /// Returns the breaking change description
+    ///
+    /// If the breaking change is only indicated by the `!` marker, the subject is used as the description.
+    pub fn breaking_change_description(&self) -> Option<&str> {
+        match &self.breaking_change {
+            Some(b) if b.is_empty() => Some(&self.subject),
+            Some(b) => Some(b),
+            None => None,
+        }
+    }
+
+    /// Returns the issue references of the footer (eg. `Closes #12`), in order of appearance
+    pub fn issues(&self) -> Vec<&IssueRef> {
+        self.footer
+            .iter()
+            .flat_map(|item| match item {
+                FooterItem::Issues(refs) => refs.as_slice(),
+                _ => &[],
+            })
+            .collect()
+    }
+
+    /// Returns the other trailers of the footer (eg. `Signed-off-by`), in order of appearance
+    pub fn trailers(&self) -> Vec<&Trailer> {
+        self.footer
+            .iter()
+            .filter_map(|item| match item {
+                FooterItem::Trailer(trailer) => Some(trailer),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the issues closed by the commit (ie. not `Refs`)
+    pub fn closed_issues(&self) -> impl Iterator<Item = &IssueRef> {
+        self.issues().into_iter().filter(|i| i.action.closes())
+    }
+
+    /// Returns the first trailer matching a token (case insensitive)
+    pub fn trailer(&self, token: &str) -> Option<&Trailer> {
+        self.trailers().into_iter().find(|t| t.is(token))
+    }
+
+    /// Returns all the trailers matching a token (case insensitive)
+    pub fn trailers_by_token<'a>(&'a self, token: &'a str) -> impl Iterator<Item = &'a Trailer> {
+        self.trailers().into_iter().filter(move |t| t.is(token))
+    }
+
+    /// Checks if the commit is a revert commit
+    pub fn is_revert(&self) -> bool {
+        self.r#type == REVERT_TYPE
+    }
+
+    /// Returns the id of the reverted commit (full or abbreviated)
+    ///
+    /// The id is read from the `This reverts commit <id>.` line written by `git revert`,
+    /// or from a `Refs` trailer.
+    pub fn reverted_commit(&self) -> Option<&str> {
+        if !self.is_revert() {
+            return None;
+        }
+
+        let body_id = self.body.as_deref().and_then(|body| {
+            body.lines().find_map(|line| {
+                line.trim()
+                    .strip_prefix("This reverts commit ")
+                    .map(|id| id.trim_end_matches('.'))
+                    .filter(|id| REGEX_COMMIT_ID.is_match(id))
+            })
+        });
+        body_id.or_else(|| {
+            self.trailers_by_token("Refs")
+                .map(|t| t.value.trim())
+                .find(|id| REGEX_COMMIT_ID.is_match(id))
+        })
+    }
+
+    /// Returns the valid type closest to an unknown type, if the edit distance is small enough
+    fn closest_type<'a>(r#type: &str, valid_types: &'a [String]) -> Option<&'a str> {
+        let max_distance = (r#type.chars().count() / 2).clamp(1, MAX_TYPE_EDIT_DISTANCE);
+        valid_types
+            .iter()
+            .map(|t| (t.as_str(), r#type.edit_distance(t)))
+            .filter(|(_, d)| *d <= max_distance)
+            .min_by_key(|(_, d)| *d)
+            .map(|(t, _)| t)
+    }
+
+    /// Returns the canonical type of an alias, with an error if it is not a valid type
+    fn resolve_alias(
+        alias: &str,
+        canonical: &str,
+        valid_types: &[String],
+        span: Span,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> String {
+        if !valid_types.iter().any(|t| t == canonical) {
+            diagnostics.push(
+                Diagnostic::error(
+                    "type-enum",
+                    &format!(
+                        "conventional commit type alias '{alias}' refers to an invalid type '{canonical}'"
+                    ),
+                    span,
+                )
+                .with_suggestion(&format!("use one of: {}", valid_types.join(", ")), None),
+            );
+        }
+        canonical.to_string()
+    }
+
+    /// Parses the 1st line
+    ///
+    /// The header is returned if the prefix can be parsed, even if there are errors.
+    fn parse_header(
+        line: &str,
+        options: &ParseOptions,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Option<CommitHeader> {
+        let valid_types = options.valid_types;
+        let rules = options.rules;
+
+        // header written by `git revert` (eg. `Revert "feat: add feature"`)
+        if let Some(reverted) = line
+            .strip_prefix("Revert \"")
+            .and_then(|s| s.strip_suffix('"'))
+        {
+            return Some(CommitHeader {
+                r#type: REVERT_TYPE.to_string(),
+                scope: None,
+                subject: reverted.to_string(),
+                breaking_change: None,
+                gitmoji: None,
+            });
+        }
+
+        // leading gitmoji (NB: before looking for the separator, as shortcodes contain colons)
+        let (mut gitmoji, start) = match options
+            .gitmoji
+            .then(|| Gitmoji::strip_prefix(line, GitmojiPosition::BeforeType))
+            .flatten()
+        {
+            Some((g, rest)) => (Some(g), line.len() - rest.len()),
+            None => (None, 0),
+        };
+
+        let colon = match line[start..].find(':') {
+            Some(i) => start + i,
+            None => {
+                diagnostics.push(
+                    Diagnostic::error(
+                        "header-format",
+                        "conventional commit missing ':' separator",
+                        Span::line(1, line),
+                    )
+                    .with_suggestion("use the format 'type(scope): subject'", None),
+                );
+                return None;
+            }
+        };
+
+        // parse the prefix
+        let prefix = &line[start..colon];
+        let capts = match REGEX_HEADER_PREFIX.captures(prefix) {
+            Some(capts) => capts,
+            None => {
+                diagnostics.push(
+                    Diagnostic::error(
+                        "header-format",
+                        &format!("invalid conventional commit prefix '{}'", prefix.trim()),
+                        Span::from_byte_range(1, line, start, colon),
+                    )
+                    .with_suggestion("use the format 'type(scope): subject'", None),
+                );
+                return None;
+            }
+        };
+
+        // get type
+        let m = capts.name("type").expect("type is always captured");
+        let mut r#type = m.as_str().to_string();
+        let type_span = Span::from_byte_range(1, line, start + m.start(), start + m.end());
+        if r#type.is_empty() {
+            diagnostics.push(Diagnostic::error(
+                "type-empty",
+                "missing conventional commit type",
+                type_span,
+            ));
+        } else if valid_types.contains(&r#type) {
+            // NB: the valid types are not aliased
+        } else if let Some(canonical) = options.aliases.get(&r#type) {
+            r#type = Self::resolve_alias(&r#type, canonical, valid_types, type_span, diagnostics);
+        } else {
+            let lowercase = r#type.to_lowercase();
+            if valid_types.contains(&lowercase) || options.aliases.contains_key(&lowercase) {
+                diagnostics.extend(
+                    rules
+                        .type_case
+                        .diagnostic(
+                            "type-case",
+                            &format!("conventional commit type '{type}' must be lowercase"),
+                            type_span,
+                        )
+                        .map(|d| {
+                            d.with_suggestion(&format!("use '{lowercase}'"), Some(&lowercase))
+                        }),
+                );
+                r#type = match options.aliases.get(&lowercase) {
+                    Some(canonical) if !valid_types.contains(&lowercase) => Self::resolve_alias(
+                        &lowercase,
+                        canonical,
+                        valid_types,
+                        type_span,
+                        diagnostics,
+                    ),
+                    _ => lowercase,
                 };
-                if let Some(issues) = &mut closed_issues {
-                    issues.push(issue_nb);
-                } else {
-                    closed_issues = Some(vec![issue_nb]);
-                }
-                // next line in body is ISSUE
-                prev_section = Section::FooterCloseIssue;
-                continue;
+            } else {
+                let message = match Self::closest_type(&lowercase, valid_types) {
+                    Some(closest) => format!(
+                        "invalid conventional commit type '{type}' (did you mean '{closest}'?)"
+                    ),
+                    None => format!("invalid conventional commit type '{type}'"),
+                };
+                diagnostics.push(
+                    Diagnostic::error("type-enum", &message, type_span)
+                        .with_suggestion(&format!("use one of: {}", valid_types.join(", ")), None),
+                );
             }
+        }
 
-            // breaking change multiline
-            if prev_section == Section::FooterBreakingChange {
-                // eprintln!("|> breaking change multiline");
-                // next line in footer is part of BREAKING CHANGE
-                if let Some(b) = &mut breaking_change {
-                    b.push('\n');
-                    b.push_str(&line);
+        // get scope
+        let scope = match capts.name("scope") {
+            Some(m) => {
+                let s = m.as_str().trim();
+                let scope_span = Span::from_byte_range(1, line, start + m.start(), start + m.end());
+                if s.is_empty() {
+                    diagnostics.push(Diagnostic::error(
+                        "scope-empty",
+                        "scope must not be empty",
+                        scope_span,
+                    ));
+                    None
                 } else {
-                    unreachable!("breaking change should be set");
+                    // NB: several scopes can be separated by commas (eg. `ui,api`)
+                    let scopes: Vec<&str> = s.split(',').map(|s| s.trim()).collect();
+                    if scopes
+                        .iter()
+                        .any(|s| s.is_empty() || !s.chars().all(is_scope_char))
+                    {
+                        diagnostics.push(Diagnostic::error(
+                            "scope-format",
+                            &format!("scope ({s}) contains invalid characters"),
+                            scope_span,
+                        ));
+                    }
+                    // check case
+                    let case = rules.scope_case.value;
+                    if !scopes
+                        .iter()
+                        .filter(|s| !s.is_empty())
+                        .all(|s| case.check(s))
+                    {
+                        let replacement = m
+                            .as_str()
+                            .split(',')
+                            .map(|part| match part.trim() {
+                                "" => part.to_string(),
+                                s => part.replacen(s, &case.apply(s), 1),
+                            })
+                            .collect::<Vec<_>>()
+                            .join(",");
+                        let fixed = replacement.trim();
+                        diagnostics.extend(
+                            rules
+                                .scope_case
+                                .level
+                                .diagnostic(
+                                    "scope-case",
+                                    &format!("scope ({s}) must {case}"),
+                                    scope_span,
+                                )
+                                .map(|d| {
+                                    d.with_suggestion(&format!("use '{fixed}'"), Some(&replacement))
+                                }),
+                        );
+                    }
+                    Some(s.to_string())
                 }
-                continue;
             }
+            None => None,
+        };
 
-            if prev_section == Section::Body {
-                // eprintln!("|> body line");
-                let mut b = if let Some(body_inner) = &body {
-                    let mut b = body_inner.clone();
-                    b.push('\n');
-                    b
+        // get breaking change indicator
+        let breaking_change = if capts["breaking"].is_empty() {
+            None
+        } else {
+            Some("".to_string())
+        };
+
+        // process subject
+        let subject_raw = &line[colon + 1..];
+        let mut subject = subject_raw.trim().to_string();
+        let mut subject_start = colon + 1 + (subject_raw.len() - subject_raw.trim_start().len());
+        if options.gitmoji && gitmoji.is_none() {
+            let rest = &line[subject_start..];
+            if let Some((g, stripped)) = Gitmoji::strip_prefix(rest, GitmojiPosition::BeforeSubject)
+            {
+                gitmoji = Some(g);
+                subject_start += rest.len() - stripped.len();
+                subject = stripped.trim().to_string();
+            }
+        }
+        let case = rules.subject_case.value;
+        match subject.chars().next() {
+            None => {
+                diagnostics.push(Diagnostic::error(
+                    "subject-empty",
+                    "subject must not be empty",
+                    Span::from_byte_range(1, line, subject_start, subject_start),
+                ));
+            }
+            Some(c) if !case.check(&subject) => {
+                // NB: for 1st character rules, only the 1st character is replaced
+                let (span_end, replacement) = if case.is_first_char() {
+                    (subject_start + c.len_utf8(), case.apply(&c.to_string()))
                 } else {
-                    "".to_string()
+                    (subject_start + subject.len(), case.apply(&subject))
                 };
-
-                b.push_str(&line);
-                body = Some(b);
-                continue;
+                let fixed = case.apply(&subject);
+                let span = Span::from_byte_range(1, line, subject_start, span_end);
+                diagnostics.extend(
+                    rules
+                        .subject_case
+                        .level
+                        .diagnostic("subject-case", &format!("subject must {case}"), span)
+                        .map(|d| {
+                            // NB: no mechanical fix if the case cannot be applied (eg. digits)
+                            if case.check(&fixed) {
+                                d.with_suggestion(&format!("use '{fixed}'"), Some(&replacement))
+                            } else {
+                                d
+                            }
+                        }),
+                );
             }
-
-            unreachable!("Unreachable line");
+            Some(_) => {}
         }
 
-        Ok(ConventionalCommitMessage {
+        Some(CommitHeader {
             r#type,
             scope,
             subject,
-            body,
             breaking_change,
-            closed_issues,
+            gitmoji,
         })
     }
+
+    /// Returns the index of the first footer line
+    ///
+    /// The footer is made of the last paragraphs which only contain trailers.
+    fn footer_start(lines: &[&str], regex_trailer: &Regex) -> usize {
+        // paragraphs start indexes
+        let mut paragraphs = Vec::<usize>::new();
+        for (i, line) in lines.iter().enumerate() {
+            if !line.trim().is_empty() && (i == 0 || lines[i - 1].trim().is_empty()) {
+                paragraphs.push(i);
+            }
+        }
+
+        let mut start = lines.len();
+        let mut end = lines.len();
+        for &p in paragraphs.iter().rev() {
+            if !Self::is_footer_paragraph(&lines[p..end], regex_trailer) {
+                break;
+            }
+            start = p;
+            end = p;
+        }
+        start
+    }
+
+    /// Checks if a paragraph only contains trailers
+    ///
+    /// Continuation lines must start with whitespace, except for a breaking change
+    /// whose description may span several lines.
+    fn is_footer_paragraph(lines: &[&str], regex_trailer: &Regex) -> bool {
+        let mut in_breaking_change = false;
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(trailer) = Trailer::parse_line(line, regex_trailer) {
+                in_breaking_change = trailer.is_breaking_change();
+                continue;
+            }
+            let is_continuation = i > 0 && line.starts_with(char::is_whitespace);
+            if !is_continuation && !in_breaking_change {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Parses the footer lines into trailers, along with their line index
+    fn parse_trailers(lines: &[&str], regex_trailer: &Regex) -> Vec<(usize, Trailer)> {
+        let mut trailers = Vec::<(usize, Trailer)>::new();
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match (
+                Trailer::parse_line(line, regex_trailer),
+                trailers.last_mut(),
+            ) {
+                (Some(trailer), _) => trailers.push((i, trailer)),
+                (None, Some((_, trailer))) => {
+                    trailer.value.push('\n');
+                    trailer.value.push_str(line);
+                }
+                (None, None) => unreachable!("footer must start with a trailer"),
+            }
+        }
+        trailers
+    }
 }
@@ -0,0 +1,225 @@
+//! Issue references
+//!
+//! Issues are referenced in the footer with the `Closes`, `Fixes`, `Resolves` and `Refs` tokens:
+//!
+//! ```text
+//! Closes #12
+//! Fixes: #13, !45
+//! Refs: org/repo#12, PROJ-123
+//! ```
+
+use std::fmt::Display;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::IssuesConfig,
+    conventional::{Trailer, TrailerSeparator},
+};
+
+/// Regex of the forge references (`#12`, `!45`, `org/repo#12`)
+static REGEX_REF: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?P<repo>[\w.-]+/[\w.-]+)?(?P<sigil>[#!])(?P<number>\d+)$")
+        .expect("Invalid regex")
+});
+
+/// Issue action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueAction {
+    /// `Closes`
+    Closes,
+    /// `Fixes`
+    Fixes,
+    /// `Resolves`
+    Resolves,
+    /// `Refs` (the issue is not closed)
+    Refs,
+}
+
+impl Display for IssueAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IssueAction::Closes => write!(f, "Closes"),
+            IssueAction::Fixes => write!(f, "Fixes"),
+            IssueAction::Resolves => write!(f, "Resolves"),
+            IssueAction::Refs => write!(f, "Refs"),
+        }
+    }
+}
+
+impl IssueAction {
+    /// All the actions
+    pub const ALL: [IssueAction; 4] = [
+        IssueAction::Closes,
+        IssueAction::Fixes,
+        IssueAction::Resolves,
+        IssueAction::Refs,
+    ];
+
+    /// Parses a trailer token (case insensitive)
+    pub fn from_token(token: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|a| a.to_string().eq_ignore_ascii_case(token))
+    }
+
+    /// Checks if the issue is closed by the commit
+    pub fn closes(&self) -> bool {
+        *self != IssueAction::Refs
+    }
+}
+
+/// Issue identifier
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueId {
+    /// Issue or pull request (`#12`)
+    Issue(u32),
+    /// Merge request (`!45`)
+    MergeRequest(u32),
+    /// Issue tracker key (eg. `PROJ-123`)
+    Key(String),
+}
+
+impl Display for IssueId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IssueId::Issue(n) => write!(f, "#{n}"),
+            IssueId::MergeRequest(n) => write!(f, "!{n}"),
+            IssueId::Key(key) => write!(f, "{key}"),
+        }
+    }
+}
+
+/// Issue reference (eg. `Closes org/repo#12`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueRef {
+    /// Action
+    pub action: IssueAction,
+    /// Repository, for cross-repository references (eg. `org/repo`)
+    pub repo: Option<String>,
+    /// Issue identifier
+    pub id: IssueId,
+}
+
+/// Displays the reference, without the action (eg. `org/repo#12`)
+impl Display for IssueRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.repo {
+            Some(repo) => write!(f, "{repo}{}", self.id),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
+impl IssueRef {
+    /// Creates a new reference to an issue of the repository
+    pub fn new(action: IssueAction, id: IssueId) -> Self {
+        Self {
+            action,
+            repo: None,
+            id,
+        }
+    }
+
+    /// Parses a single reference (eg. `#12`, `!45`, `org/repo#12` or `PROJ-123`)
+    ///
+    /// Tracker keys must match one of the key patterns (cf. [IssuesConfig::key_regexes]).
+    pub fn parse(action: IssueAction, s: &str, key_patterns: &[Regex]) -> Option<Self> {
+        if let Some(capts) = REGEX_REF.captures(s) {
+            let number = capts["number"].parse::<u32>().ok()?;
+            let id = match &capts["sigil"] {
+                "!" => IssueId::MergeRequest(number),
+                _ => IssueId::Issue(number),
+            };
+            return Some(Self {
+                action,
+                repo: capts.name("repo").map(|m| m.as_str().to_string()),
+                id,
+            });
+        }
+
+        key_patterns
+            .iter()
+            .any(|p| p.is_match(s))
+            .then(|| Self::new(action, IssueId::Key(s.to_string())))
+    }
+
+    /// Parses a list of references separated by commas or whitespace
+    ///
+    /// Returns `None` if a reference is invalid.
+    pub fn parse_list(action: IssueAction, s: &str, key_patterns: &[Regex]) -> Option<Vec<Self>> {
+        let refs: Option<Vec<_>> = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .map(|s| Self::parse(action, s, key_patterns))
+            .collect();
+        refs.filter(|refs| !refs.is_empty())
+    }
+
+    /// Parses the references of a trailer, or returns `None` if the token is not an issue action
+    ///
+    /// The inner option is `None` if a reference is invalid.
+    pub(crate) fn from_trailer(
+        trailer: &Trailer,
+        key_patterns: &[Regex],
+    ) -> Option<(IssueAction, Option<Vec<Self>>)> {
+        let action = IssueAction::from_token(&trailer.token)?;
+        let value = match trailer.separator {
+            TrailerSeparator::Hash => format!("#{}", trailer.value),
+            TrailerSeparator::Colon => trailer.unfolded_value(),
+        };
+        Some((action, Self::parse_list(action, &value, key_patterns)))
+    }
+
+    /// Returns the footer trailer (eg. `Closes #12` or `Refs: PROJ-123`)
+    pub fn to_trailer(&self) -> Trailer {
+        match (&self.repo, &self.id) {
+            (None, IssueId::Issue(n)) => Trailer {
+                token: self.action.to_string(),
+                separator: TrailerSeparator::Hash,
+                value: n.to_string(),
+            },
+            _ => Trailer::new(&self.action.to_string(), &self.to_string()),
+        }
+    }
+
+    /// Returns the URL of the issue, from the configured URL templates
+    ///
+    /// For cross-repository references, the repository URL is on the same host as the origin.
+    pub fn url(&self, origin_url: &str, config: &IssuesConfig) -> Option<String> {
+        let repo_url = match &self.repo {
+            Some(repo) => {
+                // NB: the host is the origin URL up to the 3rd slash (eg. `https://github.com`)
+                let host_end = origin_url
+                    .match_indices('/')
+                    .nth(2)
+                    .map(|(i, _)| i)
+                    .unwrap_or(origin_url.len());
+                format!("{}/{repo}", &origin_url[..host_end])
+            }
+            None => origin_url.to_string(),
+        };
+        match &self.id {
+            IssueId::Issue(n) => Some(
+                config
+                    .issue_url
+                    .replace("{{repo_url}}", &repo_url)
+                    .replace("{{id}}", &n.to_string()),
+            ),
+            IssueId::MergeRequest(n) => Some(
+                config
+                    .merge_request_url
+                    .replace("{{repo_url}}", &repo_url)
+                    .replace("{{id}}", &n.to_string()),
+            ),
+            IssueId::Key(key) => config
+                .key_url
+                .as_ref()
+                .map(|url| url.replace("{{key}}", key)),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Merge and autosquash commits
+//!
+//! Merge commits (eg. `Merge pull request #12 from ...`) and autosquash commits
+//! (`fixup!`, `squash!` and `amend!`) are not conventional commits.
+//! How they are handled in the history is configured with [CommitPoliciesConfig].
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{
+    config::{CommitPoliciesConfig, CommitPolicy},
+    git::GitCommit,
+    lint::{Diagnostic, Span},
+};
+
+/// Autosquash prefixes
+const AUTOSQUASH_PREFIXES: [&str; 3] = ["fixup! ", "squash! ", "amend! "];
+
+/// Regex of the headers of the merge commits created by git (`git merge`, `git pull`) and the forges
+/// (eg. `Merge pull request #12 from user/branch`)
+static REGEX_MERGE_HEADER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^Merge (branch(es)? '[^']+'|remote-tracking branch(es)? '[^']+'|tag '[^']+'|commit '[^']+'|pull request #\d+ from \S+)")
+        .expect("Invalid regex")
+});
+
+/// Merge or autosquash commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecialCommit<'a> {
+    /// Merge commit, with the 1st line of the body (eg. the PR title)
+    Merge(Option<&'a str>),
+    /// `fixup!` commit, with the referenced commit message
+    Fixup(&'a str),
+    /// `squash!` commit, with the referenced commit message
+    Squash(&'a str),
+    /// `amend!` commit, with the new commit message
+    Amend(&'a str),
+}
+
+impl<'a> SpecialCommit<'a> {
+    /// Detects a merge or autosquash commit from its message
+    ///
+    /// Merge commits are detected from the headers created by git and the forges
+    /// (eg. `Merge branch 'dev'`), other headers starting with `Merge` are regular commits.
+    pub fn detect(message: &'a str) -> Option<Self> {
+        let header = message.lines().next().unwrap_or_default();
+
+        // merge commit
+        if REGEX_MERGE_HEADER.is_match(header) {
+            return Some(Self::merge(message));
+        }
+
+        // autosquash commit
+        let prefix = AUTOSQUASH_PREFIXES
+            .iter()
+            .find(|p| header.starts_with(*p))?;
+        let referenced = Self::strip_autosquash_prefixes(message);
+        match *prefix {
+            "fixup! " => Some(SpecialCommit::Fixup(referenced)),
+            "squash! " => Some(SpecialCommit::Squash(referenced)),
+            _ => {
+                // NB: the body of an `amend!` commit is the new commit message
+                let new_message = referenced
+                    .split_once("\n\n")
+                    .map(|(_, body)| body.trim())
+                    .filter(|body| !body.is_empty())
+                    .unwrap_or(referenced);
+                Some(SpecialCommit::Amend(new_message))
+            }
+        }
+    }
+
+    /// Detects a merge or autosquash commit of the history
+    ///
+    /// Commits with several parents are merge commits, whatever their message.
+    pub fn detect_commit(commit: &'a GitCommit) -> Option<Self> {
+        if commit.merge {
+            return Some(Self::merge(&commit.message));
+        }
+        Self::detect(&commit.message)
+    }
+
+    /// Creates a merge commit, with the 1st line of its body
+    fn merge(message: &'a str) -> Self {
+        let pr_title = message
+            .lines()
+            .skip(1)
+            .map(|l| l.trim())
+            .find(|l| !l.is_empty());
+        SpecialCommit::Merge(pr_title)
+    }
+
+    /// Strips the autosquash prefixes (eg. `fixup! fixup! feat: ...`)
+    fn strip_autosquash_prefixes(mut message: &str) -> &str {
+        while let Some(rest) = AUTOSQUASH_PREFIXES
+            .iter()
+            .find_map(|p| message.strip_prefix(p))
+        {
+            message = rest;
+        }
+        message
+    }
+
+    /// Checks if the commit is an autosquash commit
+    pub fn is_autosquash(&self) -> bool {
+        !matches!(self, SpecialCommit::Merge(_))
+    }
+
+    /// Returns the configured policy for this kind of commit
+    pub fn policy(&self, policies: &CommitPoliciesConfig) -> CommitPolicy {
+        match self {
+            SpecialCommit::Merge(_) => policies.merge,
+            SpecialCommit::Fixup(_) => policies.fixup,
+            SpecialCommit::Squash(_) => policies.squash,
+            SpecialCommit::Amend(_) => policies.amend,
+        }
+    }
+
+    /// Returns the commit message to use in the history, or `None` if the commit is skipped
+    pub fn resolve(&self, policies: &CommitPoliciesConfig) -> Option<&'a str> {
+        match (self, self.policy(policies)) {
+            (SpecialCommit::Merge(pr_title), CommitPolicy::PrTitle) => *pr_title,
+            (SpecialCommit::Fixup(msg), CommitPolicy::Referenced)
+            | (SpecialCommit::Squash(msg), CommitPolicy::Referenced)
+            | (SpecialCommit::Amend(msg), CommitPolicy::Referenced) => Some(msg),
+            _ => None,
+        }
+    }
+
+    /// Returns the diagnostic for an autosquash commit which has not been squashed
+    pub fn autosquash_diagnostic(&self, message: &str) -> Option<Diagnostic> {
+        if !self.is_autosquash() {
+            return None;
+        }
+        let header = message.lines().next().unwrap_or_default();
+        let prefix_len = header.len() - Self::strip_autosquash_prefixes(header).len();
+        let prefix = header[..prefix_len].trim_end();
+        Some(
+            Diagnostic::error(
+                "autosquash",
+                &format!("'{prefix}' commits must be squashed before merging"),
+                Span::from_byte_range(1, header, 0, prefix.len()),
+            )
+            .with_suggestion("run 'git rebase -i --autosquash'", None),
+        )
+    }
+}
@@ -18,13 +18,34 @@ pub enum Error {
     InternalError(String),
     #[error("{0}")]
     NoCommits(String),
+    #[error("{0}")]
+    NoReleaseNeeded(String),
     #[error("Template error: {0}")]
-    TemplateError(#[from] handlebars::TemplateError),
+    TemplateError(Box<handlebars::TemplateError>),
     #[error("Template render error: {0}")]
-    TemplateRenderError(#[from] handlebars::RenderError),
+    TemplateRenderError(Box<handlebars::RenderError>),
     #[error("Invalid hook: {0}")]
     InvalidHook(String),
 }
 
+impl From<handlebars::TemplateError> for Error {
+    fn from(err: handlebars::TemplateError) -> Self {
+        Error::TemplateError(Box::new(err))
+    }
+}
+
+impl From<handlebars::RenderError> for Error {
+    fn from(err: handlebars::RenderError) -> Self {
+        Error::TemplateRenderError(Box::new(err))
+    }
+}
+
+impl Error {
+    /// Checks if the error means that there is nothing to release
+    pub fn is_no_release(&self) -> bool {
+        matches!(self, Error::NoCommits(_) | Error::NoReleaseNeeded(_))
+    }
+}
+
 /// Crate result type
 pub type Result<T> = std::result::Result<T, Error>;
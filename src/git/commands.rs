@@ -1,15 +1,19 @@
 //! Wrappers for git commands
 
 pub mod add;
+pub mod branch;
 pub mod commit;
 pub mod config;
+pub mod log;
 pub mod push;
 pub mod status;
 pub mod tag;
 
 pub use add::*;
+pub use branch::*;
 pub use commit::*;
 pub use config::*;
+pub use log::*;
 pub use push::*;
 pub use status::*;
 pub use tag::*;
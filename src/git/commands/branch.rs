@@ -0,0 +1,27 @@
+//! Wrapper for `git branch` commands.
+
+use std::process::Command;
+
+use crate::error::{Error, Result};
+
+/// Wrapper for `git branch --show-current`
+///
+/// Returns `None` if HEAD is detached.
+pub fn git_current_branch() -> Result<Option<String>> {
+    let output = Command::new("git")
+        .args(["branch", "--show-current"])
+        .output()?;
+    if !output.status.success() {
+        return Err(Error::InternalError(format!(
+            "Failed to get the current branch: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if branch.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(branch))
+    }
+}
@@ -28,6 +28,21 @@ pub fn get_config_origin_url() -> Result<String> {
     Ok(stdout.to_string())
 }
 
+/// Returns the comment character of the commit messages (`core.commentChar`, default `#`).
+///
+/// NB: with `auto`, git picks a character which is not used in the message, `#` is returned.
+pub fn get_config_comment_char() -> String {
+    let output = Command::new("git")
+        .args(["config", "--get", "core.commentChar"])
+        .output()
+        .expect("Failed to execute command");
+    let comment_char = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || comment_char.is_empty() || comment_char == "auto" {
+        return "#".to_string();
+    }
+    comment_char
+}
+
 /// Sets the git hooks directory.
 ///
 /// `git config core.hookspath ${dir}`
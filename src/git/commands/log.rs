@@ -0,0 +1,262 @@
+//! Wrappers for `git log` commands
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use chrono::{DateTime, FixedOffset, Utc};
+
+use crate::{
+    error::{Error, Result},
+    git::{GitCommit, SignatureStatus},
+};
+
+/// Runs `git log` and returns the commits
+///
+/// ## Notes
+///
+/// `git log id1..`: get all logs from ref `id1` (exclusive) to HEAD
+///
+/// `git log id1..id2`: get all logs from ref `id1` (exclusive) to the ref `id2` (inclusive)
+pub fn git_log(log_range: &str) -> Result<Vec<GitCommit>> {
+    LogQuery::new().range(log_range).run()
+}
+
+/// Query for `git log`, with the commits filters
+///
+/// ```no_run
+/// # use gitext::git::LogQuery;
+/// let commits = LogQuery::new()
+///     .range("v1.0.0..")
+///     .path("docs")
+///     .no_merges()
+///     .run()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LogQuery {
+    /// Repo directory (defaults to the current directory)
+    dir: Option<PathBuf>,
+    /// Revision range (eg. `v1.0.0..HEAD`)
+    range: Option<String>,
+    /// Paths, relative to the repo root
+    paths: Vec<PathBuf>,
+    /// Author patterns
+    authors: Vec<String>,
+    /// Follow only the first parent of merge commits
+    first_parent: bool,
+    /// Exclude merge commits
+    no_merges: bool,
+    /// Commits more recent than a date
+    since: Option<DateTime<Utc>>,
+    /// Commits older than a date
+    until: Option<DateTime<Utc>>,
+}
+
+impl LogQuery {
+    /// Creates a query for all the commits reachable from HEAD
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `git log` in a repo directory, instead of the current directory
+    pub fn dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets the revision range (an empty range is ignored)
+    pub fn range(mut self, range: &str) -> Self {
+        self.range = (!range.is_empty()).then(|| range.to_string());
+        self
+    }
+
+    /// Only returns the commits changing files in a path, relative to the repo root
+    ///
+    /// The path can be any git pathspec (eg. `docs/*.md`).
+    pub fn path(mut self, path: impl AsRef<Path>) -> Self {
+        self.paths.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Only returns the commits changing files in one of the paths (cf. [LogQuery::path])
+    pub fn paths<P: AsRef<Path>>(mut self, paths: impl IntoIterator<Item = P>) -> Self {
+        self.paths
+            .extend(paths.into_iter().map(|p| p.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Only returns the commits of an author (pattern matching the name or email)
+    ///
+    /// With several authors, the commits of any of them are returned.
+    pub fn author(mut self, author: &str) -> Self {
+        self.authors.push(author.to_string());
+        self
+    }
+
+    /// Follows only the first parent of merge commits (`--first-parent`)
+    pub fn first_parent(mut self) -> Self {
+        self.first_parent = true;
+        self
+    }
+
+    /// Excludes the merge commits (`--no-merges`)
+    pub fn no_merges(mut self) -> Self {
+        self.no_merges = true;
+        self
+    }
+
+    /// Only returns the commits more recent than a date (`--since`, on the commit date)
+    pub fn since(mut self, date: DateTime<Utc>) -> Self {
+        self.since = Some(date);
+        self
+    }
+
+    /// Only returns the commits older than a date (`--until`, on the commit date)
+    pub fn until(mut self, date: DateTime<Utc>) -> Self {
+        self.until = Some(date);
+        self
+    }
+
+    /// Returns the `git log` arguments
+    fn args(&self) -> Vec<String> {
+        let mut args = vec![
+            // NB: the file names are not quoted
+            "-c".to_string(),
+            "core.quotePath=false".to_string(),
+            "log".to_string(),
+            "-z".to_string(),
+            format!("--format={LOG_FORMAT}"),
+            "--name-only".to_string(),
+        ];
+        for author in &self.authors {
+            args.push(format!("--author={author}"));
+        }
+        if self.first_parent {
+            args.push("--first-parent".to_string());
+        }
+        if self.no_merges {
+            args.push("--no-merges".to_string());
+        }
+        if let Some(since) = self.since {
+            args.push(format!("--since={}", since.to_rfc3339()));
+        }
+        if let Some(until) = self.until {
+            args.push(format!("--until={}", until.to_rfc3339()));
+        }
+        if let Some(range) = &self.range {
+            args.push(range.clone());
+        }
+        if !self.paths.is_empty() {
+            args.push("--".to_string());
+            // NB: the paths are relative to the repo root, whatever the current directory
+            args.extend(self.paths.iter().map(|p| format!(":(top){}", p.display())));
+        }
+        args
+    }
+
+    /// Runs `git log` and returns the commits, with their changed files
+    pub fn run(&self) -> Result<Vec<GitCommit>> {
+        let mut command = Command::new("git");
+        if let Some(dir) = &self.dir {
+            command.current_dir(dir);
+        }
+        let output = command.args(self.args()).output()?;
+        if !output.status.success() {
+            return Err(Error::InternalError(format!(
+                "Failed to get git logs: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        parse_log(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// Format of the commits
+///
+/// NB: each commit starts with a record separator, followed by NUL-terminated fields,
+/// so that the messages (which cannot contain NUL) are not confused with the fields.
+const LOG_FORMAT: &str = "%x1e%H%x00%P%x00%an%x00%ae%x00%aI%x00%cn%x00%ce%x00%cI%x00%G?%x00%B%x00";
+
+/// Number of fields of [LOG_FORMAT]
+const LOG_FIELDS: usize = 10;
+
+/// Parses the output of `git log -z --name-only` with [LOG_FORMAT]
+///
+/// Each commit is followed by an empty field, and its changed files (the 1st file is prefixed with a newline).
+fn parse_log(output: &str) -> Result<Vec<GitCommit>> {
+    let mut commits: Vec<GitCommit> = Vec::new();
+    let mut fields = output.split('\0').peekable();
+    while let Some(field) = fields.next() {
+        let id = match field.strip_prefix('\x1e') {
+            Some(id) => id,
+            None if field.is_empty() => continue,
+            None => {
+                return Err(Error::InternalError(format!(
+                    "Invalid git log output, expected a commit: {field:?}"
+                )));
+            }
+        };
+
+        let mut values = vec![id];
+        for _ in 1..LOG_FIELDS {
+            match fields.next() {
+                Some(value) => values.push(value),
+                None => {
+                    return Err(Error::InternalError(format!(
+                        "Invalid git log output, missing fields for commit {id}"
+                    )));
+                }
+            }
+        }
+        let parents: Vec<_> = values[1].split_whitespace().map(String::from).collect();
+        let signature = SignatureStatus::from_code(values[8]).ok_or_else(|| {
+            Error::InternalError(format!(
+                "Invalid signature status '{}' for commit {id}",
+                values[8]
+            ))
+        })?;
+
+        let mut files = Vec::<PathBuf>::new();
+        while let Some(file) = fields.next_if(|f| !f.starts_with('\x1e')) {
+            if file.is_empty() {
+                continue;
+            }
+            let file = match files.is_empty() {
+                true => file.strip_prefix('\n').unwrap_or(file),
+                false => file,
+            };
+            files.push(PathBuf::from(file));
+        }
+
+        commits.push(GitCommit {
+            id: id.to_string(),
+            merge: parents.len() > 1,
+            parents,
+            timestamp: parse_date(values[4], id)?,
+            author: values[2].to_string(),
+            author_email: values[3].to_string(),
+            committer: values[5].to_string(),
+            committer_email: values[6].to_string(),
+            committer_timestamp: parse_date(values[7], id)?,
+            signature,
+            message: values[9]
+                .strip_suffix('\n')
+                .unwrap_or(values[9])
+                .to_string(),
+            files,
+        });
+    }
+
+    Ok(commits)
+}
+
+/// Parses a commit date (strict ISO 8601)
+fn parse_date(date: &str, id: &str) -> Result<DateTime<Utc>> {
+    DateTime::<FixedOffset>::parse_from_rfc3339(date)
+        .map(|d| d.with_timezone(&Utc))
+        .map_err(|err| {
+            Error::InternalError(format!("Invalid date '{date}' for commit {id}: {err}"))
+        })
+}
@@ -37,7 +37,8 @@ pub fn git_get_tags() -> Result<Vec<GitTag>> {
             "Failed to get git tag refs".to_string(),
         ));
     }
-    let hash_ref_map: HashMap<_, _> = String::from_utf8_lossy(&output_show_ref.stdout)
+    // NB: several tags can point to the same commit (eg. the tags of packages released together)
+    let hash_refs: Vec<_> = String::from_utf8_lossy(&output_show_ref.stdout)
         .lines()
         .map(|line| {
             let parts: Vec<_> = line.splitn(2, ' ').collect();
@@ -51,7 +52,7 @@ pub fn git_get_tags() -> Result<Vec<GitTag>> {
         .collect();
 
     let mut annotated_tags_commits: HashMap<String, String> = HashMap::new();
-    for (hash, r#ref) in hash_ref_map {
+    for (hash, r#ref) in hash_refs {
         if r#ref.ends_with("^{}") {
             let tag_short_ref = r#ref
                 .strip_prefix("refs/tags/")
@@ -1,6 +1,6 @@
 //! Wrappers for git commits
 
-use std::fmt::Display;
+use std::{fmt::Display, path::PathBuf};
 
 use chrono::{DateTime, Utc};
 
@@ -9,21 +9,45 @@ use chrono::{DateTime, Utc};
 pub struct GitCommit {
     /// Commit id (hash)
     pub id: String,
-    /// Commit timestamp
+    /// Parent commits ids
+    pub parents: Vec<String>,
+    /// Commit timestamp (author date)
     pub timestamp: DateTime<Utc>,
     /// Author
     pub author: String,
+    /// Author email
+    pub author_email: String,
+    /// Committer
+    pub committer: String,
+    /// Committer email
+    pub committer_email: String,
+    /// Committer timestamp
+    pub committer_timestamp: DateTime<Utc>,
+    /// Signature status
+    pub signature: SignatureStatus,
+    /// If set, the commit is a merge commit (with several parents)
+    pub merge: bool,
     /// Message
     pub message: String,
+    /// Changed files, relative to the repo root
+    pub files: Vec<PathBuf>,
 }
 
 impl Default for GitCommit {
     fn default() -> Self {
         Self {
             id: Default::default(),
+            parents: Default::default(),
             timestamp: Utc::now(),
             author: Default::default(),
+            author_email: Default::default(),
+            committer: Default::default(),
+            committer_email: Default::default(),
+            committer_timestamp: Utc::now(),
+            signature: Default::default(),
+            merge: Default::default(),
             message: Default::default(),
+            files: Default::default(),
         }
     }
 }
@@ -37,3 +61,47 @@ impl Display for GitCommit {
         Ok(())
     }
 }
+
+/// Signature status of a commit (cf. `git log --format=%G?`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignatureStatus {
+    /// Good signature (`G`)
+    Good,
+    /// Bad signature (`B`)
+    Bad,
+    /// Good signature, with an unknown validity (`U`)
+    UnknownValidity,
+    /// Good signature, which has expired (`X`)
+    Expired,
+    /// Good signature, made by an expired key (`Y`)
+    ExpiredKey,
+    /// Good signature, made by a revoked key (`R`)
+    Revoked,
+    /// Signature which cannot be checked, eg. with a missing key (`E`)
+    Unverifiable,
+    /// No signature (`N`)
+    #[default]
+    None,
+}
+
+impl SignatureStatus {
+    /// Parses the status code of `%G?`
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "G" => Some(Self::Good),
+            "B" => Some(Self::Bad),
+            "U" => Some(Self::UnknownValidity),
+            "X" => Some(Self::Expired),
+            "Y" => Some(Self::ExpiredKey),
+            "R" => Some(Self::Revoked),
+            "E" => Some(Self::Unverifiable),
+            "N" => Some(Self::None),
+            _ => None,
+        }
+    }
+
+    /// Checks if the commit is signed (whether the signature is valid or not)
+    pub fn is_signed(&self) -> bool {
+        *self != Self::None
+    }
+}
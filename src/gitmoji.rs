@@ -0,0 +1,237 @@
+//! Gitmoji support
+//!
+//! Refer to [https://gitmoji.dev](https://gitmoji.dev)
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Gitmoji list (emoji, shortcode, description)
+pub const GITMOJIS: [(&str, &str, &str); 75] = [
+    ("🎨", "art", "Improve structure / format of the code"),
+    ("⚡️", "zap", "Improve performance"),
+    ("🔥", "fire", "Remove code or files"),
+    ("🐛", "bug", "Fix a bug"),
+    ("🚑️", "ambulance", "Critical hotfix"),
+    ("✨", "sparkles", "Introduce new features"),
+    ("📝", "memo", "Add or update documentation"),
+    ("🚀", "rocket", "Deploy stuff"),
+    ("💄", "lipstick", "Add or update the UI and style files"),
+    ("🎉", "tada", "Begin a project"),
+    ("✅", "white_check_mark", "Add, update, or pass tests"),
+    ("🔒️", "lock", "Fix security or privacy issues"),
+    ("🔐", "closed_lock_with_key", "Add or update secrets"),
+    ("🔖", "bookmark", "Release / Version tags"),
+    ("🚨", "rotating_light", "Fix compiler / linter warnings"),
+    ("🚧", "construction", "Work in progress"),
+    ("💚", "green_heart", "Fix CI Build"),
+    ("⬇️", "arrow_down", "Downgrade dependencies"),
+    ("⬆️", "arrow_up", "Upgrade dependencies"),
+    ("📌", "pushpin", "Pin dependencies to specific versions"),
+    ("👷", "construction_worker", "Add or update CI build system"),
+    (
+        "📈",
+        "chart_with_upwards_trend",
+        "Add or update analytics or track code",
+    ),
+    ("♻️", "recycle", "Refactor code"),
+    ("➕", "heavy_plus_sign", "Add a dependency"),
+    ("➖", "heavy_minus_sign", "Remove a dependency"),
+    ("🔧", "wrench", "Add or update configuration files"),
+    ("🔨", "hammer", "Add or update development scripts"),
+    (
+        "🌐",
+        "globe_with_meridians",
+        "Internationalization and localization",
+    ),
+    ("✏️", "pencil2", "Fix typos"),
+    ("💩", "poop", "Write bad code that needs to be improved"),
+    ("⏪️", "rewind", "Revert changes"),
+    ("🔀", "twisted_rightwards_arrows", "Merge branches"),
+    ("📦️", "package", "Add or update compiled files or packages"),
+    ("👽️", "alien", "Update code due to external API changes"),
+    ("🚚", "truck", "Move or rename resources"),
+    ("📄", "page_facing_up", "Add or update license"),
+    ("💥", "boom", "Introduce breaking changes"),
+    ("🍱", "bento", "Add or update assets"),
+    ("♿️", "wheelchair", "Improve accessibility"),
+    ("💡", "bulb", "Add or update comments in source code"),
+    ("🍻", "beers", "Write code drunkenly"),
+    ("💬", "speech_balloon", "Add or update text and literals"),
+    ("🗃️", "card_file_box", "Perform database related changes"),
+    ("🔊", "loud_sound", "Add or update logs"),
+    ("🔇", "mute", "Remove logs"),
+    ("👥", "busts_in_silhouette", "Add or update contributor(s)"),
+    (
+        "🚸",
+        "children_crossing",
+        "Improve user experience / usability",
+    ),
+    ("🏗️", "building_construction", "Make architectural changes"),
+    ("📱", "iphone", "Work on responsive design"),
+    ("🤡", "clown_face", "Mock things"),
+    ("🥚", "egg", "Add or update an easter egg"),
+    ("🙈", "see_no_evil", "Add or update a .gitignore file"),
+    ("📸", "camera_flash", "Add or update snapshots"),
+    ("⚗️", "alembic", "Perform experiments"),
+    ("🔍️", "mag", "Improve SEO"),
+    ("🏷️", "label", "Add or update types"),
+    ("🌱", "seedling", "Add or update seed files"),
+    (
+        "🚩",
+        "triangular_flag_on_post",
+        "Add, update, or remove feature flags",
+    ),
+    ("🥅", "goal_net", "Catch errors"),
+    ("💫", "dizzy", "Add or update animations and transitions"),
+    (
+        "🗑️",
+        "wastebasket",
+        "Deprecate code that needs to be cleaned up",
+    ),
+    (
+        "🛂",
+        "passport_control",
+        "Work on code related to authorization, roles and permissions",
+    ),
+    (
+        "🩹",
+        "adhesive_bandage",
+        "Simple fix for a non-critical issue",
+    ),
+    ("🧐", "monocle_face", "Data exploration/inspection"),
+    ("⚰️", "coffin", "Remove dead code"),
+    ("🧪", "test_tube", "Add a failing test"),
+    ("👔", "necktie", "Add or update business logic"),
+    ("🩺", "stethoscope", "Add or update healthcheck"),
+    ("🧱", "bricks", "Infrastructure related changes"),
+    ("🧑‍💻", "technologist", "Improve developer experience"),
+    (
+        "💸",
+        "money_with_wings",
+        "Add sponsorships or money related infrastructure",
+    ),
+    (
+        "🧵",
+        "thread",
+        "Add or update code related to multithreading or concurrency",
+    ),
+    (
+        "🦺",
+        "safety_vest",
+        "Add or update code related to validation",
+    ),
+    ("✈️", "airplane", "Improve offline support"),
+    ("🦖", "t-rex", "Code that adds backwards compatibility"),
+];
+
+/// Position of the gitmoji in the commit header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GitmojiPosition {
+    /// `✨ feat: subject`
+    #[default]
+    BeforeType,
+    /// `feat: ✨ subject`
+    BeforeSubject,
+}
+
+/// Gitmoji format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GitmojiFormat {
+    /// Unicode emoji (eg. `✨`)
+    #[default]
+    Emoji,
+    /// Shortcode (eg. `:sparkles:`)
+    Shortcode,
+}
+
+/// Gitmoji in a commit message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Gitmoji {
+    /// Shortcode, without the colons (eg. `sparkles`)
+    pub code: String,
+    /// Position in the commit header
+    pub position: GitmojiPosition,
+    /// Format
+    pub format: GitmojiFormat,
+}
+
+impl Display for Gitmoji {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.format, self.emoji()) {
+            (GitmojiFormat::Emoji, Some(emoji)) => write!(f, "{emoji}"),
+            _ => write!(f, ":{}:", self.code),
+        }
+    }
+}
+
+impl Gitmoji {
+    /// Creates a new gitmoji from a shortcode (without the colons)
+    pub fn new(code: &str, position: GitmojiPosition, format: GitmojiFormat) -> Self {
+        Self {
+            code: code.to_string(),
+            position,
+            format,
+        }
+    }
+
+    /// Returns the unicode emoji
+    pub fn emoji(&self) -> Option<&'static str> {
+        shortcode_to_emoji(&self.code)
+    }
+
+    /// Returns the gitmoji description
+    pub fn description(&self) -> Option<&'static str> {
+        GITMOJIS
+            .iter()
+            .find(|(_, code, _)| *code == self.code)
+            .map(|(_, _, desc)| *desc)
+    }
+
+    /// Strips a leading gitmoji (emoji or shortcode) from a string
+    ///
+    /// Returns the gitmoji, and the rest of the string without the leading whitespace.
+    pub fn strip_prefix(s: &str, position: GitmojiPosition) -> Option<(Self, &str)> {
+        // shortcode
+        if let Some(rest) = s.strip_prefix(':') {
+            if let Some((code, rest)) = rest.split_once(':') {
+                if shortcode_to_emoji(code).is_some() {
+                    let gitmoji = Self::new(code, position, GitmojiFormat::Shortcode);
+                    return Some((gitmoji, rest.trim_start()));
+                }
+            }
+            return None;
+        }
+
+        // emoji (NB: the variation selector is optional)
+        for (emoji, code, _) in GITMOJIS {
+            let emoji_bare = emoji.trim_end_matches('\u{fe0f}');
+            if let Some(rest) = s.strip_prefix(emoji_bare) {
+                let rest = rest.strip_prefix('\u{fe0f}').unwrap_or(rest);
+                let gitmoji = Self::new(code, position, GitmojiFormat::Emoji);
+                return Some((gitmoji, rest.trim_start()));
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns the emoji for a shortcode (without the colons)
+pub fn shortcode_to_emoji(code: &str) -> Option<&'static str> {
+    GITMOJIS
+        .iter()
+        .find(|(_, c, _)| *c == code)
+        .map(|(emoji, _, _)| *emoji)
+}
+
+/// Returns the shortcode (without the colons) for an emoji
+pub fn emoji_to_shortcode(emoji: &str) -> Option<&'static str> {
+    let emoji = emoji.trim_end_matches('\u{fe0f}');
+    GITMOJIS
+        .iter()
+        .find(|(e, _, _)| e.trim_end_matches('\u{fe0f}') == emoji)
+        .map(|(_, code, _)| *code)
+}
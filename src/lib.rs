@@ -15,6 +15,8 @@ pub mod config;
 pub mod conventional;
 pub mod error;
 pub mod git;
+pub mod gitmoji;
 pub mod hooks;
+pub mod lint;
 pub mod utils;
 pub mod version;
@@ -0,0 +1,526 @@
+//! Commit message linting
+//!
+//! Lint diagnostics are collected when parsing a commit message
+//! (cf. [ConventionalCommitMessage::parse_with_rules](crate::conventional::ConventionalCommitMessage::parse_with_rules)).
+//!
+//! The rules are configured in the `[lint]` section of the config file (cf. [LintConfig]).
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::LintConfig,
+    conventional::{ConventionalCommitMessage, FooterItem},
+    utils::StringExt,
+};
+
+/// Diagnostic severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Warning (does not invalidate the commit)
+    Warning,
+    /// Error (invalidates the commit)
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Lint rule level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// Rule is disabled
+    Off,
+    /// Rule violations are warnings
+    Warn,
+    /// Rule violations are errors
+    Error,
+}
+
+impl LintLevel {
+    /// Returns the diagnostic severity, or `None` if the rule is disabled
+    pub fn severity(&self) -> Option<Severity> {
+        match self {
+            LintLevel::Off => None,
+            LintLevel::Warn => Some(Severity::Warning),
+            LintLevel::Error => Some(Severity::Error),
+        }
+    }
+
+    /// Checks if the rule is enabled
+    pub fn is_on(&self) -> bool {
+        *self != LintLevel::Off
+    }
+
+    /// Creates a diagnostic for this level, or `None` if the rule is disabled
+    pub fn diagnostic(&self, rule: &str, message: &str, span: Span) -> Option<Diagnostic> {
+        self.severity().map(|severity| Diagnostic {
+            rule: rule.to_string(),
+            severity,
+            message: message.to_string(),
+            span,
+            suggestion: None,
+        })
+    }
+}
+
+/// Lint rule with a value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintRule<T> {
+    /// Rule level
+    pub level: LintLevel,
+    /// Rule value
+    pub value: T,
+}
+
+impl<T> LintRule<T> {
+    /// Creates a new rule
+    pub fn new(level: LintLevel, value: T) -> Self {
+        Self { level, value }
+    }
+}
+
+/// Text case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Case {
+    /// Starts with a lowercase character
+    LowerFirst,
+    /// All lowercase
+    LowerCase,
+    /// Starts with an uppercase character
+    SentenceCase,
+    /// All uppercase
+    UpperCase,
+}
+
+impl Display for Case {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Case::LowerFirst => write!(f, "start with lowercase"),
+            Case::LowerCase => write!(f, "be lowercase"),
+            Case::SentenceCase => write!(f, "start with uppercase"),
+            Case::UpperCase => write!(f, "be uppercase"),
+        }
+    }
+}
+
+impl Case {
+    /// Checks if a string matches the case
+    pub fn check(&self, s: &str) -> bool {
+        match self {
+            Case::LowerFirst => s.starts_with_lowercase(),
+            Case::LowerCase => s == s.to_lowercase(),
+            Case::SentenceCase => s.chars().next().map(|c| c.is_uppercase()).unwrap_or(false),
+            Case::UpperCase => s == s.to_uppercase(),
+        }
+    }
+
+    /// Converts a string to the case
+    pub fn apply(&self, s: &str) -> String {
+        match self {
+            Case::LowerFirst => s.to_lowercase_first(),
+            Case::LowerCase => s.to_lowercase(),
+            Case::SentenceCase => s.to_uppercase_first(),
+            Case::UpperCase => s.to_uppercase(),
+        }
+    }
+
+    /// Checks if the case only applies to the 1st character
+    pub fn is_first_char(&self) -> bool {
+        matches!(self, Case::LowerFirst | Case::SentenceCase)
+    }
+}
+
+/// Location of a diagnostic in the commit message
+///
+/// Lines and columns start at 1, and are counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    /// Line number
+    pub line: usize,
+    /// Column of the 1st character
+    pub column: usize,
+    /// Number of characters
+    pub length: usize,
+}
+
+impl Span {
+    /// Creates a new span
+    pub fn new(line: usize, column: usize, length: usize) -> Self {
+        Self {
+            line,
+            column,
+            length,
+        }
+    }
+
+    /// Creates a span from a byte range within a line
+    pub fn from_byte_range(line_nb: usize, line: &str, start: usize, end: usize) -> Self {
+        Self {
+            line: line_nb,
+            column: line[..start].chars().count() + 1,
+            length: line[start..end].chars().count(),
+        }
+    }
+
+    /// Creates a span covering a whole line
+    pub fn line(line_nb: usize, line: &str) -> Self {
+        Self::from_byte_range(line_nb, line, 0, line.len())
+    }
+
+    /// Returns the byte range of the span in the source, or `None` if it is out of bounds
+    pub fn byte_range(&self, source: &str) -> Option<(usize, usize)> {
+        let mut line_start = 0;
+        for (i, line) in source.split_inclusive('\n').enumerate() {
+            if i + 1 == self.line {
+                let line = line.trim_end_matches(['\n', '\r']);
+                let mut offsets = line
+                    .char_indices()
+                    .map(|(i, _)| i)
+                    .chain(std::iter::once(line.len()));
+                let start = offsets.nth(self.column.checked_sub(1)?)?;
+                let end = match self.length {
+                    0 => start,
+                    n => offsets.nth(n - 1)?,
+                };
+                return Some((line_start + start, line_start + end));
+            }
+            line_start += line.len();
+        }
+        None
+    }
+}
+
+/// Suggested fix for a diagnostic
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Suggestion {
+    /// Suggestion message
+    pub message: String,
+    /// Text replacing the span, if the fix is mechanical
+    pub replacement: Option<String>,
+}
+
+/// Lint diagnostic
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Rule id (eg. `subject-case`)
+    pub rule: String,
+    /// Severity
+    pub severity: Severity,
+    /// Message
+    pub message: String,
+    /// Location in the commit message
+    pub span: Span,
+    /// Suggested fix
+    pub suggestion: Option<Suggestion>,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.rule, self.message)
+    }
+}
+
+impl Diagnostic {
+    /// Creates a new diagnostic with an error severity
+    pub fn error(rule: &str, message: &str, span: Span) -> Self {
+        Self {
+            rule: rule.to_string(),
+            severity: Severity::Error,
+            message: message.to_string(),
+            span,
+            suggestion: None,
+        }
+    }
+
+    /// Adds a suggestion
+    pub fn with_suggestion(mut self, message: &str, replacement: Option<&str>) -> Self {
+        self.suggestion = Some(Suggestion {
+            message: message.to_string(),
+            replacement: replacement.map(|r| r.to_string()),
+        });
+        self
+    }
+
+    /// Checks if the diagnostic is an error
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// Returns the text replacing the span, if the fix is mechanical
+    pub fn replacement(&self) -> Option<&str> {
+        self.suggestion.as_ref()?.replacement.as_deref()
+    }
+
+    /// Renders the offending line with a caret under the span, compiler style
+    ///
+    /// ```text
+    ///  --> 1:6
+    ///   |
+    /// 1 | fix: Commit subject
+    ///   |      ^
+    ///   = help: subject must start with lowercase
+    /// ```
+    pub fn render_snippet(&self, source: &str) -> String {
+        let line = source.lines().nth(self.span.line - 1).unwrap_or_default();
+        let line_nb = self.span.line.to_string();
+        let gutter = " ".repeat(line_nb.len());
+
+        let mut s = String::new();
+        s.push_str(&format!(
+            "{gutter}--> {}:{}\n",
+            self.span.line, self.span.column
+        ));
+        s.push_str(&format!("{gutter} |\n"));
+        s.push_str(&format!("{line_nb} | {line}\n"));
+        s.push_str(&format!(
+            "{gutter} | {}{}",
+            " ".repeat(self.span.column - 1),
+            "^".repeat(self.span.length.max(1))
+        ));
+        if let Some(suggestion) = &self.suggestion {
+            s.push_str(&format!("\n{gutter} = help: {}", suggestion.message));
+        }
+        s
+    }
+}
+
+/// Lint report (eg. for the JSON output of `git-lint`)
+#[derive(Debug, Serialize)]
+pub struct LintReport {
+    /// Checks if the commit message has no errors
+    pub valid: bool,
+    /// Parsed commit message, if the header can be parsed
+    pub commit: Option<ConventionalCommitMessage>,
+    /// Lint diagnostics
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl LintReport {
+    /// Creates a new report
+    pub fn new(commit: Option<ConventionalCommitMessage>, diagnostics: Vec<Diagnostic>) -> Self {
+        Self {
+            valid: !diagnostics.iter().any(|d| d.is_error()),
+            commit,
+            diagnostics,
+        }
+    }
+}
+
+/// Applies the mechanical fixes of the diagnostics to the source
+///
+/// Fixes are applied from the end of the message, and overlapping fixes are skipped
+/// (cf. [ConventionalCommitMessage::fix] to fix a message until it is stable).
+pub fn apply_fixes(source: &str, diagnostics: &[Diagnostic]) -> String {
+    let mut edits: Vec<(usize, usize, &str)> = diagnostics
+        .iter()
+        .filter_map(|d| {
+            let replacement = d.replacement()?;
+            let (start, end) = d.span.byte_range(source)?;
+            Some((start, end, replacement))
+        })
+        .collect();
+    edits.sort_by(|a, b| b.cmp(a));
+    edits.dedup();
+
+    let mut fixed = source.to_string();
+    let mut next_start = source.len();
+    for (start, end, replacement) in edits {
+        if end > next_start {
+            continue;
+        }
+        fixed.replace_range(start..end, replacement);
+        next_start = start;
+    }
+    fixed
+}
+
+/// Scissors line of `git commit --verbose`, after the comment character (the diff is below)
+const SCISSORS_LINE: &str = "------------------------ >8 ------------------------";
+
+/// Strips the comment lines of a commit message file, as git does (eg. in the `commit-msg` hook)
+///
+/// The lines starting with the comment character are removed, with everything below the scissors
+/// line of `git commit --verbose`, and the trailing empty lines.
+pub fn strip_comments(message: &str, comment_char: &str) -> String {
+    let mut lines = Vec::<&str>::new();
+    for line in message.lines() {
+        match line.strip_prefix(comment_char) {
+            Some(comment) if comment.trim() == SCISSORS_LINE => break,
+            Some(_) => {}
+            None => lines.push(line),
+        }
+    }
+    let stripped = lines.join("\n");
+    let stripped = stripped.trim_end();
+    if stripped.is_empty() {
+        String::new()
+    } else {
+        format!("{stripped}\n")
+    }
+}
+
+/// Renders a line diff between 2 versions of a commit message
+///
+/// Removed lines start with `-`, added lines with `+`, and unchanged lines with a space.
+pub fn render_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // length of the longest common subsequence, from each pair of lines
+    let mut lcs = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::<String>::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(format!(" {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(format!("-{}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+{}", new[j]));
+            j += 1;
+        }
+    }
+    lines.join("\n")
+}
+
+/// Checks the message-level rules (lengths, full stop, required scope, body and footer)
+pub(crate) fn check_rules(
+    commit: &ConventionalCommitMessage,
+    lines: &[&str],
+    rules: &LintConfig,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let header = lines.first().copied().unwrap_or_default();
+    let header_span = Span::line(1, header);
+
+    // header length
+    let max_length = rules.header_max_length.value;
+    let header_length = header.chars().count();
+    if header_length > max_length {
+        let span = Span::new(1, max_length + 1, header_length - max_length);
+        diagnostics.extend(rules.header_max_length.level.diagnostic(
+            "header-max-length",
+            &format!("header must not be longer than {max_length} characters ({header_length})"),
+            span,
+        ));
+    }
+
+    // trailing period
+    if let Some(stripped) = header.trim_end().strip_suffix('.') {
+        let span = Span::new(1, stripped.chars().count() + 1, 1);
+        diagnostics.extend(
+            rules
+                .subject_full_stop
+                .diagnostic(
+                    "subject-full-stop",
+                    "subject must not end with a period",
+                    span,
+                )
+                .map(|d| d.with_suggestion("remove the period", Some(""))),
+        );
+    }
+
+    // scope
+    if commit.scope.is_none() {
+        diagnostics.extend(rules.scope_required.diagnostic(
+            "scope-required",
+            "scope is required",
+            header_span,
+        ));
+    }
+    let scopes_start = header.find('(').map(|i| i + 1).unwrap_or_default();
+    for scope in commit.scopes() {
+        if !rules.scope_enum.value.iter().any(|s| s == scope) {
+            let span = match header[scopes_start..].find(scope) {
+                Some(i) => Span::from_byte_range(
+                    1,
+                    header,
+                    scopes_start + i,
+                    scopes_start + i + scope.len(),
+                ),
+                None => header_span,
+            };
+            diagnostics.extend(
+                rules
+                    .scope_enum
+                    .level
+                    .diagnostic(
+                        "scope-enum",
+                        &format!("scope ({scope}) is not allowed"),
+                        span,
+                    )
+                    .map(|d| {
+                        d.with_suggestion(
+                            &format!("use one of: {}", rules.scope_enum.value.join(", ")),
+                            None,
+                        )
+                    }),
+            );
+        }
+    }
+
+    // body line length
+    let max_length = rules.body_max_line_length.value;
+    for (i, line) in lines.iter().enumerate().skip(1) {
+        let line_length = line.chars().count();
+        if line_length > max_length {
+            let span = Span::new(i + 1, max_length + 1, line_length - max_length);
+            diagnostics.extend(rules.body_max_line_length.level.diagnostic(
+                "body-max-line-length",
+                &format!("line must not be longer than {max_length} characters ({line_length})"),
+                span,
+            ));
+        }
+    }
+
+    // required body
+    if commit.body.is_none() && rules.body_required_types.value.contains(&commit.r#type) {
+        diagnostics.extend(rules.body_required_types.level.diagnostic(
+            "body-required",
+            &format!("body is required for '{}' commits", commit.r#type),
+            header_span,
+        ));
+    }
+
+    // required footer
+    let has_footer = commit
+        .breaking_change
+        .as_ref()
+        .map(|b| !b.is_empty())
+        .unwrap_or(false)
+        || commit
+            .footer
+            .iter()
+            .any(|item| !matches!(item, FooterItem::BreakingChange(_)));
+    if !has_footer && rules.footer_required_types.value.contains(&commit.r#type) {
+        diagnostics.extend(rules.footer_required_types.level.diagnostic(
+            "footer-required",
+            &format!("footer is required for '{}' commits", commit.r#type),
+            header_span,
+        ));
+    }
+}
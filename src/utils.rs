@@ -13,6 +13,9 @@ pub trait StringExt {
 
     /// Returns a new string starting with a first character in uppercase
     fn to_uppercase_first(&self) -> String;
+
+    /// Returns the edit distance (Levenshtein) to another string, in characters
+    fn edit_distance(&self, other: &str) -> usize;
 }
 
 impl StringExt for str {
@@ -52,4 +55,20 @@ impl StringExt for str {
             })
             .collect::<String>()
     }
+
+    fn edit_distance(&self, other: &str) -> usize {
+        let other: Vec<char> = other.chars().collect();
+        let mut prev_row: Vec<usize> = (0..=other.len()).collect();
+        for (i, c) in self.chars().enumerate() {
+            let mut row = vec![i + 1; other.len() + 1];
+            for (j, o) in other.iter().enumerate() {
+                let cost = if c == *o { 0 } else { 1 };
+                row[j + 1] = (prev_row[j] + cost)
+                    .min(prev_row[j + 1] + 1)
+                    .min(row[j] + 1);
+            }
+            prev_row = row;
+        }
+        prev_row[other.len()]
+    }
 }
@@ -1,17 +1,28 @@
 //! Version management
 
-use std::process::Command;
+pub mod manifest;
+pub mod scheme;
+pub mod tag;
 
+use std::{collections::HashSet, fmt::Display, process::Command, time::Duration};
+
+use chrono::Utc;
+use handlebars::Handlebars;
 use log::{debug, warn};
 use semver::{BuildMetadata, Prerelease, Version};
+use serde::Serialize;
+use wait_timeout::ChildExt;
 
 use crate::{
-    config::Config,
+    config::{Bump, Config, PreStablePolicy, VersionSchemeKind},
     conventional::ConventionalCommitMessage,
     error::{Error, Result},
-    git::{git_get_tags, git_log, GitTag},
+    git::{git_current_branch, git_get_tags, git_log, GitCommit, GitTag},
 };
 
+use scheme::{version_scheme, VersionScheme};
+use tag::{tag_pattern, TagPattern};
+
 /// GitTag with SemVer version information
 #[derive(Debug, Clone, Eq)]
 pub struct SemverGitTag {
@@ -29,7 +40,7 @@ impl PartialEq for SemverGitTag {
 
 impl PartialOrd for SemverGitTag {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.version.partial_cmp(&other.version)
+        Some(self.cmp(other))
     }
 }
 
@@ -57,6 +68,11 @@ impl IntoSemverGitTag for GitTag {
 pub trait IntoSemverGitTags {
     /// Converts GitTag into SemverGitTag
     fn into_semver(self) -> Result<Vec<SemverGitTag>>;
+
+    /// Converts GitTag into SemverGitTag, with a tag pattern and a version scheme
+    ///
+    /// NB: tags which do not follow the pattern or the scheme are ignored.
+    fn into_versions(self, pattern: &TagPattern, scheme: &dyn VersionScheme) -> Vec<SemverGitTag>;
 }
 
 impl IntoSemverGitTags for Vec<GitTag> {
@@ -67,35 +83,285 @@ impl IntoSemverGitTags for Vec<GitTag> {
         }
         Ok(tags)
     }
+
+    fn into_versions(self, pattern: &TagPattern, scheme: &dyn VersionScheme) -> Vec<SemverGitTag> {
+        self.into_iter()
+            .filter_map(|tag| {
+                let version = match pattern.parse(&tag.tag) {
+                    Some(version) => version,
+                    None => {
+                        debug!("Tag {} does not match the tag pattern |> skipped", tag.tag);
+                        return None;
+                    }
+                };
+                match scheme.parse(version) {
+                    Some(version) => Some(SemverGitTag { version, tag }),
+                    None => {
+                        debug!(
+                            "Tag {} does not follow the version scheme |> skipped",
+                            tag.tag
+                        );
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// Options to calculate the next version
+#[derive(Debug, Clone, Default)]
+pub struct BumpOptions {
+    /// Pre-release channel (eg. `beta`)
+    pub channel: Option<String>,
+    /// Version of the 1st release, if there is no version tag (defaults to `0.0.1` for SemVer)
+    pub initial_version: Option<String>,
+    /// Releases 1.0.0 from a pre-1.0 version
+    pub graduate: bool,
+}
+
+/// Status of a commit when calculating the next version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitStatus {
+    /// The commit is counted
+    Counted,
+    /// Merge or autosquash commit, skipped by the commit policies
+    Skipped,
+    /// Not a conventional commit
+    Unconventional,
+    /// Cancelled out by a revert
+    Cancelled,
+}
+
+impl Display for CommitStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommitStatus::Counted => write!(f, "counted"),
+            CommitStatus::Skipped => write!(f, "skipped"),
+            CommitStatus::Unconventional => write!(f, "unconventional"),
+            CommitStatus::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// Version increment of a commit
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitBump {
+    /// Commit id
+    pub id: String,
+    /// 1st line of the commit message
+    pub summary: String,
+    /// Conventional commit type
+    pub r#type: Option<String>,
+    /// Status
+    pub status: CommitStatus,
+    /// Version increment
+    pub bump: Bump,
+    /// Rule causing the increment (eg. `commit.bump.feat`)
+    pub rule: Option<String>,
+}
+
+/// Explanation of the next version
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionExplanation {
+    /// Current version (latest tag)
+    pub current: Option<String>,
+    /// Next version, or `None` if no release is needed
+    pub next: Option<String>,
+    /// Version increment
+    pub bump: Bump,
+    /// Commits since the latest stable version (latest to earliest)
+    pub commits: Vec<CommitBump>,
+    /// Id of the latest commit causing the increment
+    pub decisive_commit: Option<String>,
+}
+
+impl VersionExplanation {
+    /// Returns the commit causing the increment
+    pub fn decisive_commit(&self) -> Option<&CommitBump> {
+        let id = self.decisive_commit.as_ref()?;
+        self.commits.iter().find(|c| &c.id == id)
+    }
+
+    /// Renders the explanation as a table
+    ///
+    /// ```text
+    /// COMMIT   TYPE  STATUS   BUMP   RULE              SUMMARY
+    /// 8f1c2a7  feat  counted  minor  commit.bump.feat  feat: add endpoint
+    /// ```
+    pub fn to_table(&self) -> String {
+        let rows: Vec<[String; 6]> = self
+            .commits
+            .iter()
+            .map(|c| {
+                [
+                    c.id.chars().take(7).collect(),
+                    c.r#type.clone().unwrap_or_else(|| "-".to_string()),
+                    c.status.to_string(),
+                    c.bump.to_string(),
+                    c.rule.clone().unwrap_or_else(|| "-".to_string()),
+                    c.summary.clone(),
+                ]
+            })
+            .collect();
+        let header = ["COMMIT", "TYPE", "STATUS", "BUMP", "RULE", "SUMMARY"].map(|h| h.to_string());
+        let mut widths = [0_usize; 6];
+        for row in std::iter::once(&header).chain(&rows) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let mut lines = Vec::<String>::new();
+        for row in std::iter::once(&header).chain(&rows) {
+            let cells: Vec<_> = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .collect();
+            lines.push(cells.join("  ").trim_end().to_string());
+        }
+        lines.push("".to_string());
+        match self.decisive_commit() {
+            Some(c) => lines.push(format!(
+                "Decisive commit: {} {} ({}, {})",
+                c.id.chars().take(7).collect::<String>(),
+                c.summary,
+                c.bump,
+                c.rule.as_deref().unwrap_or_default()
+            )),
+            None => lines.push("No commit requires a version bump".to_string()),
+        }
+        lines.join("\n")
+    }
+
+    /// Renders the explanation as JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|err| Error::InternalError(format!("Cannot serialize the explanation: {err}")))
+    }
 }
 
 /// Calculates the repo next version based on the commit history
 ///
 /// ## Notes
 ///
-/// The latest version is the latest tag, sorted by SemVer version,
+/// The latest version is the latest stable tag, sorted by version (cf. the `[release] scheme` config),
 /// and all commits after that tag are considered to be part of the next version.
-pub fn get_repo_next_version(config: &Config) -> Result<(Version, Option<Version>)> {
-    let repo_version_opt = get_repo_latest_tag()?;
+/// The versions are formatted with the version scheme.
+///
+/// With a pre-release channel (eg. `beta`), the next version is a pre-release of the next stable version
+/// (eg. `1.3.0-beta.2`), numbered after the existing pre-release tags of that channel.
+/// The returned current version is the latest tag, which may be a pre-release.
+///
+/// Before 1.0.0, the increments follow the `[release] pre_stable` policy (cf. [PreStablePolicy]).
+///
+/// [Error::NoReleaseNeeded] is returned if none of the commits bumps the version
+/// (cf. the `[commit.bump]` rules).
+pub fn get_repo_next_version(
+    config: &Config,
+    options: &BumpOptions,
+) -> Result<(String, Option<String>)> {
+    let explanation = explain_repo_next_version(config, options)?;
+    match explanation.next {
+        Some(next) => Ok((next, explanation.current)),
+        None => Err(Error::NoReleaseNeeded(
+            "No release needed (no commit requires a version bump)".to_string(),
+        )),
+    }
+}
+
+/// Explains the repo next version, with the version increment of each commit
+/// (cf. [get_repo_next_version])
+pub fn explain_repo_next_version(
+    config: &Config,
+    options: &BumpOptions,
+) -> Result<VersionExplanation> {
+    let channel = options.channel.as_deref();
+    let scheme = version_scheme(config)?;
+    let tags = git_get_tags()?.into_versions(&tag_pattern(config)?, scheme.as_ref());
+    let latest_tag = tags.iter().max();
+    let latest_stable_tag = tags.iter().filter(|t| t.version.pre.is_empty()).max();
+
+    if options.initial_version.is_some() && latest_stable_tag.is_some() {
+        warn!("The initial version is ignored, as the repo has a version tag");
+    }
+
+    let initial_version = match &options.initial_version {
+        Some(v) => Some(
+            scheme
+                .parse(v)
+                .ok_or_else(|| Error::InternalError(format!("Invalid initial version '{v}'")))?,
+        ),
+        None => None,
+    };
+
+    // NB: graduating does not require new commits
+    let graduated = match latest_stable_tag {
+        _ if !options.graduate => None,
+        _ if config.release.scheme != VersionSchemeKind::Semver => {
+            return Err(Error::InternalError(
+                "Only SemVer versions can graduate".to_string(),
+            ));
+        }
+        Some(t) if t.version.major > 0 => {
+            return Err(Error::InternalError(format!(
+                "Cannot graduate, version {} is already stable",
+                t.version
+            )));
+        }
+        _ => Some(Version::new(1, 0, 0)),
+    };
 
-    let log_range = match &repo_version_opt {
+    // NB: a release of the same channel without new commits is not bumped
+    let latest_release_tag = tags
+        .iter()
+        .filter(|t| match channel {
+            Some(channel) => t.version.pre.is_empty() || is_channel_prerelease(&t.version, channel),
+            None => t.version.pre.is_empty(),
+        })
+        .max();
+    if let (Some(t), None) = (latest_release_tag, &graduated) {
+        if get_package_commits(config, &format!("{}..", t.tag.hash))?.is_empty() {
+            return Err(Error::NoCommits(
+                "Cannot bump without new commits".to_string(),
+            ));
+        }
+    }
+
+    let log_range = match latest_stable_tag {
         Some(v) => format!("{}..", v.tag.hash),
         None => "".to_string(),
     };
-    let commits = git_log(&log_range)?;
+    let commits = get_package_commits(config, &log_range)?;
 
-    if commits.is_empty() {
+    if commits.is_empty() && graduated.is_none() {
         return Err(Error::NoCommits(
             "Cannot bump without new commits".to_string(),
         ));
     }
 
-    let mut conv_commits: Vec<ConventionalCommitMessage> = vec![];
+    let mut commit_bumps = Vec::<CommitBump>::new();
+    let mut parsed_commits = Vec::<(String, ConventionalCommitMessage)>::new();
     for c in commits {
-        match ConventionalCommitMessage::parse(&c.message, &config.valid_commit_types()) {
-            Ok(conv_commit) => {
+        let mut commit_bump = CommitBump {
+            id: c.id.clone(),
+            summary: c.message.lines().next().unwrap_or_default().to_string(),
+            r#type: None,
+            status: CommitStatus::Counted,
+            bump: Bump::None,
+            rule: None,
+        };
+        match ConventionalCommitMessage::parse_log_commit(&c, config) {
+            Ok(Some(conv_commit)) => {
                 debug!("commit to version |> {}", c.message);
-                conv_commits.push(conv_commit);
+                commit_bump.r#type = Some(conv_commit.r#type.clone());
+                parsed_commits.push((c.id, conv_commit));
+            }
+            Ok(None) => {
+                debug!("Merge or autosquash commit ({}) |> skipped", c.id);
+                commit_bump.status = CommitStatus::Skipped;
             }
             Err(err) => {
                 // NB: skip invalid commits
@@ -103,63 +369,245 @@ pub fn get_repo_next_version(config: &Config) -> Result<(Version, Option<Version
                     "Invalid conventional commit ({}) |> skipped ({})",
                     c.id, err
                 );
+                commit_bump.status = CommitStatus::Unconventional;
             }
         }
+        commit_bumps.push(commit_bump);
     }
 
-    let curr_version = repo_version_opt.map(|v| v.version);
+    // NB: a commit and its revert do not change the version
+    let reverts: Vec<_> = parsed_commits
+        .iter()
+        .map(|(id, c)| (id.as_str(), c.reverted_commit()))
+        .collect();
+    let cancelled = get_cancelled_commits(&reverts);
+    for (id, c) in &parsed_commits {
+        let commit_bump = commit_bumps
+            .iter_mut()
+            .find(|b| &b.id == id)
+            .expect("parsed commits are in the range");
+        if cancelled.contains(id) {
+            commit_bump.status = CommitStatus::Cancelled;
+        } else {
+            let (bump, rule) = get_commit_bump_rule(config, c);
+            commit_bump.bump = bump;
+            commit_bump.rule = Some(rule);
+        }
+    }
 
-    let next_version = match &curr_version {
-        None => Version::new(0, 0, 1),
-        Some(curr) => {
-            let mut has_minor_change = false;
-            let mut has_major_change = false;
-            for c in conv_commits {
-                if config.type_is_minor_inc(&c.r#type) {
-                    has_minor_change = true;
-                }
-                if c.breaking_change.is_some() {
-                    has_major_change = true;
-                }
-            }
+    // NB: the decisive commit is the latest commit with the highest increment
+    let bump = commit_bumps
+        .iter()
+        .map(|c| c.bump)
+        .max()
+        .unwrap_or(Bump::None);
+    let decisive_commit = commit_bumps
+        .iter()
+        .find(|c| c.bump == bump && bump != Bump::None)
+        .map(|c| c.id.clone());
 
-            let mut next = curr.clone();
-            next.pre = Prerelease::EMPTY;
-            next.build = BuildMetadata::EMPTY;
-            if curr.major > 0 {
-                if has_major_change {
-                    next.major += 1;
-                    next.minor = 0;
-                    next.patch = 0;
-                } else if has_minor_change {
-                    next.minor += 1;
-                    next.patch = 0;
-                } else {
-                    next.patch += 1;
-                }
-            } else {
-                // pre 1.0.0
-                if has_major_change {
-                    next.minor += 1;
-                    next.patch = 0;
-                } else {
-                    next.patch += 1;
-                }
-            }
+    let next_stable = match (graduated, latest_stable_tag) {
+        (Some(v), _) => Some(v),
+        (None, None) => match initial_version {
+            Some(v) => Some(v),
+            None => Some(scheme.next_version(None, bump)?),
+        },
+        (None, Some(_)) if bump == Bump::None => None,
+        (None, Some(curr)) => Some(scheme.next_version(Some(&curr.version), bump)?),
+    };
 
-            next
+    let next = match (next_stable, channel) {
+        (Some(stable), Some(channel)) => {
+            let versions: Vec<_> = tags.iter().map(|t| t.version.clone()).collect();
+            Some(get_next_prerelease(&stable, channel, &versions)?)
         }
+        (next_stable, _) => next_stable,
     };
 
-    Ok((next_version, curr_version))
+    Ok(VersionExplanation {
+        current: latest_tag.map(|t| scheme.format(&t.version)),
+        next: next.map(|v| scheme.format(&v)),
+        bump,
+        commits: commit_bumps,
+        decisive_commit,
+    })
+}
+
+/// Increments a stable version
+///
+/// Before 1.0.0, the increment depends on the policy.
+pub fn bump_version(curr: &Version, bump: Bump, pre_stable: PreStablePolicy) -> Version {
+    let mut next = curr.clone();
+    next.pre = Prerelease::EMPTY;
+    next.build = BuildMetadata::EMPTY;
+    if curr.major > 0 {
+        match bump {
+            Bump::Major => {
+                next.major += 1;
+                next.minor = 0;
+                next.patch = 0;
+            }
+            Bump::Minor => {
+                next.minor += 1;
+                next.patch = 0;
+            }
+            Bump::Patch => {
+                next.patch += 1;
+            }
+            Bump::None => {}
+        }
+    } else {
+        // pre 1.0.0
+        match (pre_stable, bump) {
+            (PreStablePolicy::Strict, Bump::Major) => {
+                next.major = 1;
+                next.minor = 0;
+                next.patch = 0;
+            }
+            (PreStablePolicy::Strict, Bump::Minor) | (_, Bump::Major) => {
+                next.minor += 1;
+                next.patch = 0;
+            }
+            (PreStablePolicy::BreakingMinor, Bump::Minor) | (_, Bump::Patch) => {
+                next.patch += 1;
+            }
+            (_, Bump::None) => {}
+        }
+    }
+    next
+}
+
+/// Checks if a version is a pre-release of a channel (eg. `1.3.0-beta.2` for `beta`)
+fn is_channel_prerelease(version: &Version, channel: &str) -> bool {
+    prerelease_number(version, channel).is_some()
+}
+
+/// Returns the pre-release number of a version for a channel (eg. `2` for `1.3.0-beta.2`)
+fn prerelease_number(version: &Version, channel: &str) -> Option<u64> {
+    version
+        .pre
+        .as_str()
+        .strip_prefix(channel)?
+        .strip_prefix('.')?
+        .parse()
+        .ok()
+}
+
+/// Returns the next pre-release of a stable version for a channel (eg. `1.3.0-beta.3`)
+///
+/// The pre-release number follows the existing pre-releases of the channel (`versions`).
+pub fn get_next_prerelease(
+    stable: &Version,
+    channel: &str,
+    versions: &[Version],
+) -> Result<Version> {
+    let number = versions
+        .iter()
+        .filter(|v| (v.major, v.minor, v.patch) == (stable.major, stable.minor, stable.patch))
+        .filter_map(|v| prerelease_number(v, channel))
+        .max()
+        .unwrap_or(0)
+        + 1;
+    let mut next = stable.clone();
+    next.pre = Prerelease::new(&format!("{channel}.{number}"))?;
+    Ok(next)
+}
+
+/// Returns the pre-release channel, from the command argument or the current branch
+/// (cf. the `[release.channels]` config)
+pub fn get_release_channel(config: &Config, pre: Option<&str>) -> Result<Option<String>> {
+    if let Some(channel) = pre {
+        return Ok(Some(channel.to_string()));
+    }
+    Ok(git_current_branch()?.and_then(|branch| config.release.channels.get(&branch).cloned()))
+}
+
+/// Returns the version increment of a commit
+///
+/// Breaking changes are major increments, otherwise the `[commit.bump]` rules apply (cf. [Config::type_bump]).
+pub fn get_commit_bump(config: &Config, commit: &ConventionalCommitMessage) -> Bump {
+    get_commit_bump_rule(config, commit).0
+}
+
+/// Returns the version increment of a commit, and the rule which applies (cf. [get_commit_bump])
+pub fn get_commit_bump_rule(config: &Config, commit: &ConventionalCommitMessage) -> (Bump, String) {
+    if commit.is_breaking() {
+        return (Bump::Major, "breaking change".to_string());
+    }
+    config.type_bump_rule(&commit.r#type, &commit.scopes())
+}
+
+/// Returns the ids of the commits which are cancelled out by a revert
+///
+/// `commits` are the commit ids, with the reverted commit id for revert commits,
+/// ordered from latest to earliest (as returned by `git log`).
+/// A revert commit and the commit it reverts are both cancelled if they are in the list.
+///
+/// NB: if a revert is itself reverted, the original commit is kept.
+pub fn get_cancelled_commits(commits: &[(&str, Option<&str>)]) -> HashSet<String> {
+    let mut cancelled = HashSet::<String>::new();
+    for (i, (id, reverted)) in commits.iter().enumerate() {
+        let reverted = match reverted {
+            Some(r) if !cancelled.contains(*id) => r,
+            _ => continue,
+        };
+        // NB: the reverted id may be abbreviated
+        if let Some((reverted_id, _)) = commits[i + 1..]
+            .iter()
+            .find(|(other, _)| other.starts_with(reverted) && !cancelled.contains(*other))
+        {
+            cancelled.insert(id.to_string());
+            cancelled.insert(reverted_id.to_string());
+        }
+    }
+    cancelled
+}
+
+/// Returns the commits of a log range which belong to the current package (cf. [Config::current_package])
+///
+/// Without package, all the commits are returned.
+pub fn get_package_commits(config: &Config, log_range: &str) -> Result<Vec<GitCommit>> {
+    let commits = git_log(log_range)?;
+    Ok(match get_package_commit_ids(config, &commits) {
+        Some(ids) => commits
+            .into_iter()
+            .filter(|c| ids.contains(&c.id))
+            .collect(),
+        None => commits,
+    })
+}
+
+/// Returns the ids of the commits which belong to the current package, or `None` without package
+///
+/// A commit belongs to a package if it changes files in the package directory,
+/// or if the package is one of its scopes (eg. `feat(core): ...`).
+pub fn get_package_commit_ids(config: &Config, commits: &[GitCommit]) -> Option<HashSet<String>> {
+    let (name, package) = config.current_package()?;
+    // NB: the changed files have no `./` prefix
+    let path = package.path.strip_prefix(".").unwrap_or(&package.path);
+
+    let mut ids = HashSet::<String>::new();
+    for c in commits {
+        if c.files.iter().any(|f| f.starts_with(path)) {
+            ids.insert(c.id.clone());
+            continue;
+        }
+        if let Ok(Some(conv_commit)) = ConventionalCommitMessage::parse_log_commit(c, config) {
+            if conv_commit.scopes().contains(&name) {
+                ids.insert(c.id.clone());
+            }
+        }
+    }
+    Some(ids)
 }
 
 /// Returns the repo last version
 ///
-/// NB: the tags are ordered by SemVer version number, not timestamp, or tag string.
-pub fn get_repo_latest_tag() -> Result<Option<SemverGitTag>> {
+/// NB: the tags are ordered by version number, not timestamp, or tag string.
+/// Tags which do not match the tag pattern or the version scheme are ignored.
+pub fn get_repo_latest_tag(config: &Config) -> Result<Option<SemverGitTag>> {
     let tags = git_get_tags()?;
-    let mut versions = tags.into_semver()?;
+    let mut versions = tags.into_versions(&tag_pattern(config)?, version_scheme(config)?.as_ref());
 
     // sort by ascending order
     versions.sort();
@@ -168,35 +616,102 @@ pub fn get_repo_latest_tag() -> Result<Option<SemverGitTag>> {
     Ok(versions.last().cloned())
 }
 
+/// Template context of the bump commands
+#[derive(Debug, Clone, Serialize)]
+pub struct BumpContext {
+    /// Next version
+    pub version: String,
+    /// Previous version
+    pub previous_version: Option<String>,
+    /// Tag of the next version
+    pub tag: String,
+    /// Release date (`YYYY-MM-DD`)
+    pub date: String,
+    /// Major number (or 1st CalVer segment)
+    pub major: u64,
+    /// Minor number (or 2nd CalVer segment)
+    pub minor: u64,
+    /// Patch number (or 3rd CalVer segment)
+    pub patch: u64,
+}
+
+impl BumpContext {
+    /// Creates a new context, from the formatted versions
+    pub fn new(config: &Config, version: &str, previous_version: Option<&str>) -> Result<Self> {
+        let parsed = version_scheme(config)?
+            .parse(version)
+            .ok_or_else(|| Error::InternalError(format!("Invalid version '{version}'")))?;
+        Ok(Self {
+            version: version.to_string(),
+            previous_version: previous_version.map(|v| v.to_string()),
+            tag: tag_pattern(config)?.format(version),
+            date: Utc::now().format("%Y-%m-%d").to_string(),
+            major: parsed.major,
+            minor: parsed.minor,
+            patch: parsed.patch,
+        })
+    }
+}
+
 /// Executes the custom bump commands
-pub fn exec_bump_commands(config: &Config, version: &str) -> Result<Vec<String>> {
-    // execute other commands to bump the package(s) version
+///
+/// The commands and their environment variables are rendered with the [BumpContext].
+/// Commands are split into words with shell quoting rules, or run with `sh -c` (cf. [crate::config::BumpCommandConfig::shell]).
+///
+/// NB: the output of the commands is not captured.
+pub fn exec_bump_commands(config: &Config, context: &BumpContext) -> Result<Vec<String>> {
+    let mut registry = Handlebars::new();
+    registry.register_escape_fn(handlebars::no_escape);
+
     let mut executed_cmds = Vec::<String>::new();
-    for cfg_command in &config.release.bump_commands {
-        let cmd = cfg_command.replace("{{version}}", version);
-        let cmd_args: Vec<&str> = cmd.split(' ').collect();
-        let output = match Command::new(&cmd_args[0])
-            .args(&cmd_args[1..])
-            .current_dir(&config.root_dir)
-            .output()
-        {
-            Ok(output) => {
-                executed_cmds.push(cmd.clone());
-                output
-            }
-            Err(err) => {
-                return Err(Error::InternalError(format!(
-                    "✗ Failed to execute command '{cmd}': {err}"
-                )));
-            }
+    for bump_command in &config.release.bump_commands {
+        let settings = bump_command.config();
+        let cmd = registry.render_template(&settings.cmd, context)?;
+
+        let mut command = if settings.shell {
+            let mut command = Command::new("sh");
+            command.arg("-c").arg(&cmd);
+            command
+        } else {
+            let words = shell_words::split(&cmd)
+                .map_err(|err| Error::InternalError(format!("Invalid command '{cmd}': {err}")))?;
+            let (program, args) = words
+                .split_first()
+                .ok_or_else(|| Error::InternalError("Empty bump command".to_string()))?;
+            let mut command = Command::new(program);
+            command.args(args);
+            command
         };
+        command.current_dir(match &settings.cwd {
+            Some(cwd) => config.root_dir.join(cwd),
+            None => config.root_dir.clone(),
+        });
+        for (key, value) in &settings.env {
+            command.env(key, registry.render_template(value, context)?);
+        }
 
-        if !output.status.success() {
+        let mut child = command.spawn().map_err(|err| {
+            Error::InternalError(format!("Failed to execute command '{cmd}': {err}"))
+        })?;
+        let status = match settings.timeout {
+            Some(timeout) => match child.wait_timeout(Duration::from_secs(timeout))? {
+                Some(status) => status,
+                None => {
+                    child.kill()?;
+                    child.wait()?;
+                    return Err(Error::InternalError(format!(
+                        "Command '{cmd}' timed out after {timeout}s"
+                    )));
+                }
+            },
+            None => child.wait()?,
+        };
+        if !status.success() {
             return Err(Error::InternalError(format!(
-                "✗ Failed to execute command '{cmd}': {}",
-                String::from_utf8_lossy(&output.stderr)
+                "Failed to execute command '{cmd}': {status}"
             )));
         }
+        executed_cmds.push(cmd);
     }
     Ok(executed_cmds)
 }
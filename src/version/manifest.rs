@@ -0,0 +1,635 @@
+//! Manifest version bumpers
+//!
+//! The versions are edited in place, so that the formatting and the comments of the manifests are preserved.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+
+use crate::{
+    config::{BumpFile, Config, ManifestKind},
+    error::{Error, Result},
+};
+
+/// Bumps the version of the configured files (cf. the `[release] bump_files` config)
+///
+/// Returns the changed files, relative to the repo root.
+pub fn bump_manifests(config: &Config, version: &str) -> Result<Vec<PathBuf>> {
+    let mut changed = Vec::<PathBuf>::new();
+    for file in &config.release.bump_files {
+        for path in bump_file(&config.root_dir, file, version)? {
+            if !changed.contains(&path) {
+                changed.push(path);
+            }
+        }
+    }
+    Ok(changed)
+}
+
+/// Bumps the version of a file, and of its related files (eg. `Cargo.lock`)
+///
+/// Returns the changed files, relative to the root directory.
+pub fn bump_file(root_dir: &Path, file: &BumpFile, version: &str) -> Result<Vec<PathBuf>> {
+    let kind = file.kind().ok_or_else(|| {
+        Error::InternalError(format!(
+            "Unknown manifest kind for {} (set a kind or a pattern)",
+            file.path.display()
+        ))
+    })?;
+
+    let mut editor = FileEditor::new(root_dir);
+    match kind {
+        ManifestKind::Cargo => bump_cargo(&mut editor, &file.path, version)?,
+        ManifestKind::Npm => bump_npm(&mut editor, &file.path, version)?,
+        ManifestKind::Pyproject => editor.edit(&file.path, |content| {
+            set_toml_version(content, "project", version)
+                .or_else(|| set_toml_version(content, "tool.poetry", version))
+                .ok_or_else(|| version_not_found(&file.path))
+        })?,
+        ManifestKind::Helm => editor.edit(&file.path, |content| {
+            set_yaml_version(content, version).ok_or_else(|| version_not_found(&file.path))
+        })?,
+        ManifestKind::Maven => editor.edit(&file.path, |content| {
+            set_pom_version(content, version).ok_or_else(|| version_not_found(&file.path))
+        })?,
+        ManifestKind::Regex => {
+            let pattern = file.pattern.as_deref().ok_or_else(|| {
+                Error::InternalError(format!("No version pattern for {}", file.path.display()))
+            })?;
+            editor.edit(&file.path, |content| {
+                set_regex_version(content, pattern, version)?
+                    .ok_or_else(|| version_not_found(&file.path))
+            })?
+        }
+    }
+    Ok(editor.changed)
+}
+
+/// Edits the files of a directory, and tracks the changed files
+struct FileEditor<'a> {
+    /// Root directory
+    root_dir: &'a Path,
+    /// Changed files
+    changed: Vec<PathBuf>,
+}
+
+impl<'a> FileEditor<'a> {
+    /// Creates a new editor
+    fn new(root_dir: &'a Path) -> Self {
+        Self {
+            root_dir,
+            changed: vec![],
+        }
+    }
+
+    /// Checks if a file exists
+    fn exists(&self, path: &Path) -> bool {
+        self.root_dir.join(path).is_file()
+    }
+
+    /// Reads a file
+    fn read(&self, path: &Path) -> Result<String> {
+        fs::read_to_string(self.root_dir.join(path))
+            .map_err(|err| Error::InternalError(format!("Cannot read {}: {err}", path.display())))
+    }
+
+    /// Edits a file, which is written if its content is changed
+    fn edit(&mut self, path: &Path, f: impl FnOnce(&str) -> Result<String>) -> Result<()> {
+        let content = self.read(path)?;
+        let new_content = f(&content)?;
+        if new_content != content {
+            fs::write(self.root_dir.join(path), new_content)?;
+            if !self.changed.iter().any(|p| p == path) {
+                self.changed.push(path.to_path_buf());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the error for a manifest without version
+fn version_not_found(path: &Path) -> Error {
+    Error::InternalError(format!("No version found in {}", path.display()))
+}
+
+/// Bumps a `Cargo.toml` manifest, with its workspace members and the `Cargo.lock` file
+///
+/// NB: the requirements of the local dependencies (eg. `foo = { path = "../foo", version = "0.1" }`) are updated
+/// in the whole workspace, and the `Cargo.lock` file is the one of the workspace root.
+fn bump_cargo(editor: &mut FileEditor, path: &Path, version: &str) -> Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let root_manifest: toml::Value = toml::from_str(&editor.read(path)?)?;
+
+    let mut manifests = vec![path.to_path_buf()];
+    for member in cargo_workspace_members(editor.root_dir, dir, &root_manifest) {
+        if !manifests.contains(&member) {
+            manifests.push(member);
+        }
+    }
+
+    // NB: packages inheriting the workspace version are bumped with the workspace
+    let mut packages = Vec::<String>::new();
+    for manifest in &manifests {
+        let value: toml::Value = toml::from_str(&editor.read(manifest)?)?;
+        if let Some(package) = value.get("package") {
+            if let (Some(name), Some(_)) = (
+                package.get("name").and_then(|n| n.as_str()),
+                package.get("version"),
+            ) {
+                packages.push(name.to_string());
+            }
+        }
+    }
+    let has_workspace_version = root_manifest
+        .get("workspace")
+        .and_then(|w| w.get("package"))
+        .and_then(|p| p.get("version"))
+        .is_some();
+    if packages.is_empty() && !has_workspace_version {
+        return Err(version_not_found(path));
+    }
+
+    for manifest in &manifests {
+        editor.edit(manifest, |content| {
+            let mut content = content.to_string();
+            for table in ["package", "workspace.package"] {
+                if let Some(new_content) = set_toml_version(&content, table, version) {
+                    content = new_content;
+                }
+            }
+            Ok(set_cargo_dependency_versions(&content, &packages, version))
+        })?;
+    }
+
+    // NB: a member is bumped within its workspace
+    let workspace_dir = match root_manifest.get("workspace") {
+        Some(_) => dir.to_path_buf(),
+        None => cargo_workspace_root(editor, path)?.unwrap_or_else(|| dir.to_path_buf()),
+    };
+    if workspace_dir != dir {
+        let workspace_manifest = workspace_dir.join("Cargo.toml");
+        let value: toml::Value = toml::from_str(&editor.read(&workspace_manifest)?)?;
+        let mut siblings = vec![workspace_manifest];
+        siblings.extend(cargo_workspace_members(
+            editor.root_dir,
+            &workspace_dir,
+            &value,
+        ));
+        for manifest in siblings.iter().filter(|m| !manifests.contains(m)) {
+            editor.edit(manifest, |content| {
+                Ok(set_cargo_dependency_versions(content, &packages, version))
+            })?;
+        }
+    }
+
+    let lock_file = workspace_dir.join("Cargo.lock");
+    if editor.exists(&lock_file) {
+        editor.edit(&lock_file, |content| {
+            Ok(set_cargo_lock_versions(content, &packages, version))
+        })?;
+    }
+    Ok(())
+}
+
+/// Returns the directory of the workspace of a member manifest, or `None` if it is not a workspace member
+///
+/// NB: as with cargo, the workspace root is the closest parent manifest with a `[workspace]` table.
+fn cargo_workspace_root(editor: &FileEditor, path: &Path) -> Result<Option<PathBuf>> {
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    for parent in dir.ancestors().skip(1) {
+        let manifest = parent.join("Cargo.toml");
+        if !editor.exists(&manifest) {
+            continue;
+        }
+        let value: toml::Value = toml::from_str(&editor.read(&manifest)?)?;
+        if value.get("workspace").is_some() {
+            let members = cargo_workspace_members(editor.root_dir, parent, &value);
+            return Ok(members
+                .iter()
+                .any(|m| m == path)
+                .then(|| parent.to_path_buf()));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the manifests of the workspace members (cf. `[workspace] members`)
+///
+/// NB: only the `dir/*` globs are supported.
+fn cargo_workspace_members(root_dir: &Path, dir: &Path, manifest: &toml::Value) -> Vec<PathBuf> {
+    let workspace = match manifest.get("workspace") {
+        Some(workspace) => workspace,
+        None => return vec![],
+    };
+    let list = |key: &str| -> Vec<String> {
+        workspace
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.trim_end_matches('/').to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let excluded: Vec<PathBuf> = list("exclude").iter().map(|e| dir.join(e)).collect();
+
+    let mut member_dirs = Vec::<PathBuf>::new();
+    for member in list("members") {
+        match member.strip_suffix("/*") {
+            Some(parent) => {
+                let mut sub_dirs: Vec<_> = fs::read_dir(root_dir.join(dir).join(parent))
+                    .map(|entries| {
+                        entries
+                            .filter_map(|e| e.ok())
+                            .map(|e| dir.join(parent).join(e.file_name()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                sub_dirs.sort();
+                member_dirs.extend(sub_dirs);
+            }
+            None => member_dirs.push(dir.join(member)),
+        }
+    }
+
+    member_dirs
+        .into_iter()
+        .filter(|d| !excluded.contains(d))
+        .map(|d| d.join("Cargo.toml"))
+        .filter(|m| root_dir.join(m).is_file())
+        .collect()
+}
+
+/// Returns the regex of the TOML table headers (cf. [toml_header])
+fn toml_header_regex() -> Regex {
+    Regex::new(r"^\s*\[([^\[\]]+)\]\s*(#.*)?$").expect("Invalid regex")
+}
+
+/// Returns the name of a TOML table header (eg. `package` for `[package]`), or `None` if the line is not a header
+///
+/// NB: arrays of tables (eg. `[[package]]`) have an empty name.
+fn toml_header(line: &str, regex_header: &Regex) -> Option<String> {
+    if !line.trim_start().starts_with('[') {
+        return None;
+    }
+    Some(
+        regex_header
+            .captures(line.trim_end())
+            .map(|capts| capts[1].trim().to_string())
+            .unwrap_or_default(),
+    )
+}
+
+/// Sets the `version` key of a TOML table (eg. `package`), or returns `None` if it is not found
+fn set_toml_version(content: &str, table: &str, version: &str) -> Option<String> {
+    let regex_version =
+        Regex::new(r#"^(\s*version\s*=\s*)("[^"]*"|'[^']*')"#).expect("Invalid regex");
+    let regex_header = toml_header_regex();
+
+    let mut in_table = false;
+    let mut found = false;
+    let mut new_content = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        if let Some(header) = toml_header(line, &regex_header) {
+            in_table = header == table;
+        } else if in_table && !found {
+            if let Some(capts) = regex_version.captures(line) {
+                let quote = &capts[2][..1];
+                new_content.push_str(&format!("{}{quote}{version}{quote}", &capts[1]));
+                new_content.push_str(&line[capts[0].len()..]);
+                found = true;
+                continue;
+            }
+        }
+        new_content.push_str(line);
+    }
+    found.then_some(new_content)
+}
+
+/// Sets the version requirement of the local dependencies, in the inline tables of the dependency tables
+fn set_cargo_dependency_versions(content: &str, packages: &[String], version: &str) -> String {
+    let regex_dep = Regex::new(
+        r#"^(?P<start>\s*"?(?P<name>[\w-]+)"?\s*=\s*\{.*\bversion\s*=\s*"(?P<op>[=^~]?))(?P<req>[^"]*)(?P<end>".*)$"#,
+    )
+    .expect("Invalid regex");
+    let regex_package = Regex::new(r#"\bpackage\s*=\s*"([^"]*)""#).expect("Invalid regex");
+    let regex_header = toml_header_regex();
+
+    let mut in_deps = false;
+    let mut new_content = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        if let Some(header) = toml_header(line, &regex_header) {
+            in_deps = header.ends_with("dependencies");
+        } else if in_deps && line.contains("path") {
+            let (text, eol) = match line.strip_suffix('\n') {
+                Some(text) => (text, "\n"),
+                None => (line, ""),
+            };
+            if let Some(capts) = regex_dep.captures(text) {
+                // NB: the dependency may be renamed
+                let name = regex_package
+                    .captures(text)
+                    .map(|c| c[1].to_string())
+                    .unwrap_or_else(|| capts["name"].to_string());
+                if packages.contains(&name) {
+                    new_content.push_str(&format!(
+                        "{}{version}{}{eol}",
+                        &capts["start"], &capts["end"]
+                    ));
+                    continue;
+                }
+            }
+        }
+        new_content.push_str(line);
+    }
+    new_content
+}
+
+/// Sets the version of the local packages in a `Cargo.lock` file
+///
+/// NB: local packages have no `source`.
+fn set_cargo_lock_versions(content: &str, packages: &[String], version: &str) -> String {
+    let names: Vec<String> = packages.iter().map(|p| format!("\"{p}\"")).collect();
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let mut new_content = String::with_capacity(content.len());
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].trim_end() != "[[package]]" {
+            new_content.push_str(lines[i]);
+            i += 1;
+            continue;
+        }
+
+        let end = lines[i + 1..]
+            .iter()
+            .position(|l| l.trim().is_empty() || l.trim_end() == "[[package]]")
+            .map(|p| i + 1 + p)
+            .unwrap_or(lines.len());
+        let block = &lines[i..end];
+        let is_local = !block.iter().any(|l| l.starts_with("source = "));
+        let is_bumped = block.iter().any(|l| {
+            l.strip_prefix("name = ")
+                .map(|n| names.iter().any(|name| n.trim_end() == name))
+                .unwrap_or(false)
+        });
+        for line in block {
+            match line.strip_prefix("version = ") {
+                Some(_) if is_local && is_bumped => {
+                    let eol = if line.ends_with('\n') { "\n" } else { "" };
+                    new_content.push_str(&format!("version = \"{version}\"{eol}"));
+                }
+                _ => new_content.push_str(line),
+            }
+        }
+        i = end;
+    }
+    new_content
+}
+
+/// Bumps a `package.json` file (and the `package-lock.json` next to it), or a `package-lock.json` file
+fn bump_npm(editor: &mut FileEditor, path: &Path, version: &str) -> Result<()> {
+    let lock_file = match path.file_name().and_then(|n| n.to_str()) {
+        Some("package-lock.json") => path.to_path_buf(),
+        _ => {
+            editor.edit(path, |content| {
+                set_json_versions(content, &[&["version"]], version)
+                    .ok_or_else(|| version_not_found(path))
+            })?;
+            path.with_file_name("package-lock.json")
+        }
+    };
+    if editor.exists(&lock_file) {
+        editor.edit(&lock_file, |content| {
+            set_json_versions(
+                content,
+                &[&["version"], &["packages", "", "version"]],
+                version,
+            )
+            .ok_or_else(|| version_not_found(&lock_file))
+        })?;
+    }
+    Ok(())
+}
+
+/// Sets the string values of a JSON document at the key paths (eg. `["packages", "", "version"]`)
+///
+/// Returns `None` if the 1st key path is not found.
+fn set_json_versions(content: &str, key_paths: &[&[&str]], version: &str) -> Option<String> {
+    let mut ranges: Vec<(usize, usize)> = key_paths
+        .iter()
+        .enumerate()
+        .map(
+            |(i, keys)| match JsonScanner::new(content).find_string(keys) {
+                Some(range) => Ok(Some(range)),
+                None if i == 0 => Err(()),
+                None => Ok(None),
+            },
+        )
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .ok()?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    // NB: replaced from the end, to keep the ranges valid
+    ranges.sort();
+    let mut new_content = content.to_string();
+    for (start, end) in ranges.into_iter().rev() {
+        new_content.replace_range(start..end, version);
+    }
+    Some(new_content)
+}
+
+/// Minimal JSON scanner, to locate values without parsing the document
+struct JsonScanner<'a> {
+    /// Document
+    s: &'a [u8],
+    /// Position
+    pos: usize,
+}
+
+impl<'a> JsonScanner<'a> {
+    /// Creates a new scanner
+    fn new(s: &'a str) -> Self {
+        Self {
+            s: s.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    /// Skips the whitespaces
+    fn skip_ws(&mut self) {
+        while self.pos < self.s.len() && self.s[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes a byte
+    fn expect(&mut self, b: u8) -> Option<()> {
+        self.skip_ws();
+        (self.s.get(self.pos) == Some(&b)).then(|| self.pos += 1)
+    }
+
+    /// Scans a string, and returns the range of its content
+    fn string(&mut self) -> Option<(usize, usize)> {
+        self.expect(b'"')?;
+        let start = self.pos;
+        while self.pos < self.s.len() {
+            match self.s[self.pos] {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    return Some((start, self.pos - 1));
+                }
+                _ => self.pos += 1,
+            }
+        }
+        None
+    }
+
+    /// Skips a value
+    fn skip_value(&mut self) -> Option<()> {
+        self.skip_ws();
+        match self.s.get(self.pos)? {
+            b'"' => self.string().map(|_| ()),
+            b'{' | b'[' => {
+                let mut depth = 0;
+                while self.pos < self.s.len() {
+                    match self.s[self.pos] {
+                        b'"' => {
+                            self.string()?;
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                self.pos += 1;
+                                return Some(());
+                            }
+                        }
+                        _ => {}
+                    }
+                    self.pos += 1;
+                }
+                None
+            }
+            _ => {
+                while self.pos < self.s.len()
+                    && !matches!(self.s[self.pos], b',' | b'}' | b']')
+                    && !self.s[self.pos].is_ascii_whitespace()
+                {
+                    self.pos += 1;
+                }
+                Some(())
+            }
+        }
+    }
+
+    /// Finds the string value at a key path, and returns the range of its content
+    fn find_string(&mut self, keys: &[&str]) -> Option<(usize, usize)> {
+        let (key, keys) = match keys.split_first() {
+            Some(split) => split,
+            None => return self.string(),
+        };
+
+        self.expect(b'{')?;
+        loop {
+            self.skip_ws();
+            if self.s.get(self.pos) == Some(&b'}') {
+                return None;
+            }
+            let (start, end) = self.string()?;
+            self.expect(b':')?;
+            if &self.s[start..end] == key.as_bytes() {
+                return self.find_string(keys);
+            }
+            self.skip_value()?;
+            self.skip_ws();
+            if self.s.get(self.pos) == Some(&b',') {
+                self.pos += 1;
+            }
+        }
+    }
+}
+
+/// Sets the top-level `version` key of a YAML document (eg. `Chart.yaml`), or returns `None` if it is not found
+fn set_yaml_version(content: &str, version: &str) -> Option<String> {
+    let regex_version = Regex::new(r#"(?m)^(?P<start>version:[ \t]*(?P<quote>["']?))[^"'\s#]+"#)
+        .expect("Invalid regex");
+    let capts = regex_version.captures(content)?;
+    let end = capts.get(0)?.end();
+    Some(format!(
+        "{}{}{version}{}",
+        &content[..capts.get(0)?.start()],
+        &capts["start"],
+        &content[end..]
+    ))
+}
+
+/// Sets the project `<version>` of a `pom.xml` file, or returns `None` if it is not found
+///
+/// NB: the versions of the parent and of the dependencies are not changed.
+fn set_pom_version(content: &str, version: &str) -> Option<String> {
+    let regex_tag = Regex::new(
+        r"(?s)<!--.*?-->|<\?.*?\?>|<!\[CDATA\[.*?\]\]>|<!.*?>|<(?P<close>/?)(?P<name>[\w.:-]+)[^>]*?(?P<empty>/?)>",
+    )
+    .expect("Invalid regex");
+
+    let mut depth = 0;
+    for capts in regex_tag.captures_iter(content) {
+        let name = match capts.name("name") {
+            Some(name) => name.as_str(),
+            None => continue,
+        };
+        if !capts["close"].is_empty() {
+            depth -= 1;
+        } else if capts["empty"].is_empty() {
+            if depth == 1 && name == "version" {
+                let start = capts.get(0)?.end();
+                let end = start + content[start..].find("</version>")?;
+                return Some(format!("{}{version}{}", &content[..start], &content[end..]));
+            }
+            depth += 1;
+        }
+    }
+    None
+}
+
+/// Sets the version of all the matches of a regex, with a `version` group (or the 1st group)
+///
+/// Returns `None` if the regex does not match.
+fn set_regex_version(content: &str, pattern: &str, version: &str) -> Result<Option<String>> {
+    let regex = Regex::new(pattern).map_err(|err| {
+        Error::InternalError(format!("Invalid version pattern '{pattern}': {err}"))
+    })?;
+    if regex.captures_len() < 2 {
+        return Err(Error::InternalError(format!(
+            "Invalid version pattern '{pattern}': no version group"
+        )));
+    }
+    let has_version_group = regex.capture_names().any(|n| n == Some("version"));
+
+    let mut new_content = String::with_capacity(content.len());
+    let mut last = 0;
+    for capts in regex.captures_iter(content) {
+        let group = match has_version_group {
+            true => capts.name("version"),
+            false => capts.get(1),
+        };
+        if let Some(group) = group {
+            new_content.push_str(&content[last..group.start()]);
+            new_content.push_str(version);
+            last = group.end();
+        }
+    }
+    if last == 0 && !regex.is_match(content) {
+        return Ok(None);
+    }
+    new_content.push_str(&content[last..]);
+    Ok(Some(new_content))
+}
@@ -0,0 +1,289 @@
+//! Version schemes
+//!
+//! Versions are represented as [Version]s, so that they can be sorted whatever the scheme.
+//! For CalVer, the format segments are the major, minor and patch numbers
+//! (eg. `2024.05.2` for `YYYY.0M.MICRO` is `2024.5.2`).
+
+use chrono::{Datelike, NaiveDate, Utc};
+use semver::{Prerelease, Version};
+
+use crate::{
+    config::{Bump, Config, PreStablePolicy, VersionSchemeKind},
+    error::{Error, Result},
+};
+
+use super::bump_version;
+
+/// Default CalVer format
+pub const DEFAULT_CALVER_FORMAT: &str = "YYYY.0M.MICRO";
+
+/// Version scheme (eg. SemVer or CalVer)
+pub trait VersionScheme {
+    /// Parses a version (without the tag prefix), or returns `None` if it does not follow the scheme
+    fn parse(&self, s: &str) -> Option<Version>;
+
+    /// Formats a version
+    fn format(&self, version: &Version) -> String;
+
+    /// Returns the next stable version, from the latest stable version and the increment
+    ///
+    /// Without a stable version, the initial version is returned.
+    fn next_version(&self, curr: Option<&Version>, bump: Bump) -> Result<Version>;
+}
+
+/// Returns the version scheme of the repo (cf. the `[release] scheme` config)
+pub fn version_scheme(config: &Config) -> Result<Box<dyn VersionScheme>> {
+    match config.release.scheme {
+        VersionSchemeKind::Semver => Ok(Box::new(SemVer::new(config.release.pre_stable))),
+        VersionSchemeKind::Calver => Ok(Box::new(CalVer::new(
+            config
+                .release
+                .calver_format
+                .as_deref()
+                .unwrap_or(DEFAULT_CALVER_FORMAT),
+        )?)),
+    }
+}
+
+/// Semantic versioning
+#[derive(Debug, Clone)]
+pub struct SemVer {
+    /// Versioning policy before 1.0.0
+    pre_stable: PreStablePolicy,
+}
+
+impl SemVer {
+    /// Creates a new scheme
+    pub fn new(pre_stable: PreStablePolicy) -> Self {
+        Self { pre_stable }
+    }
+}
+
+impl VersionScheme for SemVer {
+    fn parse(&self, s: &str) -> Option<Version> {
+        Version::parse(s).ok()
+    }
+
+    fn format(&self, version: &Version) -> String {
+        version.to_string()
+    }
+
+    fn next_version(&self, curr: Option<&Version>, bump: Bump) -> Result<Version> {
+        Ok(match curr {
+            Some(curr) => bump_version(curr, bump, self.pre_stable),
+            None => Version::new(0, 0, 1),
+        })
+    }
+}
+
+/// CalVer format token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalVerToken {
+    /// Full year (`2024`)
+    FullYear,
+    /// Short year (`24`, `106`)
+    ShortYear,
+    /// Zero-padded year (`24`, `06`)
+    ZeroPaddedYear,
+    /// Month (`5`)
+    Month,
+    /// Zero-padded month (`05`)
+    ZeroPaddedMonth,
+    /// ISO week (`9`)
+    Week,
+    /// Zero-padded ISO week (`09`)
+    ZeroPaddedWeek,
+    /// Day (`7`)
+    Day,
+    /// Zero-padded day (`07`)
+    ZeroPaddedDay,
+    /// Incremented number, for several releases within the same period
+    Micro,
+}
+
+impl CalVerToken {
+    /// Parses a format token (eg. `YYYY` or `0M`)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "YYYY" => Some(CalVerToken::FullYear),
+            "YY" => Some(CalVerToken::ShortYear),
+            "0Y" => Some(CalVerToken::ZeroPaddedYear),
+            "MM" => Some(CalVerToken::Month),
+            "0M" => Some(CalVerToken::ZeroPaddedMonth),
+            "WW" => Some(CalVerToken::Week),
+            "0W" => Some(CalVerToken::ZeroPaddedWeek),
+            "DD" => Some(CalVerToken::Day),
+            "0D" => Some(CalVerToken::ZeroPaddedDay),
+            "MICRO" => Some(CalVerToken::Micro),
+            _ => None,
+        }
+    }
+
+    /// Returns the width of the zero-padded segment
+    fn width(&self) -> Option<usize> {
+        match self {
+            CalVerToken::FullYear => Some(4),
+            CalVerToken::ZeroPaddedYear
+            | CalVerToken::ZeroPaddedMonth
+            | CalVerToken::ZeroPaddedWeek
+            | CalVerToken::ZeroPaddedDay => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Returns the value for a date, or `None` for `MICRO`
+    fn value(&self, date: NaiveDate) -> Option<u64> {
+        match self {
+            CalVerToken::FullYear => Some(date.year() as u64),
+            CalVerToken::ShortYear | CalVerToken::ZeroPaddedYear => {
+                Some((date.year() - 2000).max(0) as u64)
+            }
+            CalVerToken::Month | CalVerToken::ZeroPaddedMonth => Some(date.month() as u64),
+            CalVerToken::Week | CalVerToken::ZeroPaddedWeek => Some(date.iso_week().week() as u64),
+            CalVerToken::Day | CalVerToken::ZeroPaddedDay => Some(date.day() as u64),
+            CalVerToken::Micro => None,
+        }
+    }
+
+    /// Checks if a value is valid for the token
+    fn is_valid(&self, value: u64) -> bool {
+        match self {
+            CalVerToken::Month | CalVerToken::ZeroPaddedMonth => (1..=12).contains(&value),
+            CalVerToken::Week | CalVerToken::ZeroPaddedWeek => (1..=53).contains(&value),
+            CalVerToken::Day | CalVerToken::ZeroPaddedDay => (1..=31).contains(&value),
+            _ => true,
+        }
+    }
+
+    /// Parses a version segment
+    fn parse_segment(&self, s: &str) -> Option<u64> {
+        if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let valid_width = match self.width() {
+            Some(width) => s.len() == width,
+            // NB: no leading zero
+            None => s == "0" || !s.starts_with('0'),
+        };
+        let value = s.parse().ok()?;
+        (valid_width && self.is_valid(value)).then_some(value)
+    }
+
+    /// Formats a version segment
+    fn format_segment(&self, value: u64) -> String {
+        match self.width() {
+            Some(width) => format!("{value:0width$}"),
+            None => value.to_string(),
+        }
+    }
+}
+
+/// Calendar versioning (eg. `2024.05.2` for `YYYY.0M.MICRO`)
+#[derive(Debug, Clone)]
+pub struct CalVer {
+    /// Format tokens
+    tokens: Vec<CalVerToken>,
+    /// Release date
+    date: NaiveDate,
+}
+
+impl CalVer {
+    /// Creates a new scheme from a format (eg. `YYYY.0M.MICRO`), for releases made today
+    ///
+    /// The format has up to 3 segments, and `MICRO` can only be the last one.
+    pub fn new(format: &str) -> Result<Self> {
+        let invalid_format =
+            |msg: &str| Error::InternalError(format!("Invalid CalVer format '{format}': {msg}"));
+
+        let tokens = format
+            .split('.')
+            .map(|s| {
+                CalVerToken::parse(s).ok_or_else(|| invalid_format(&format!("unknown token '{s}'")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if tokens.len() > 3 {
+            return Err(invalid_format("more than 3 segments"));
+        }
+        if tokens[..tokens.len() - 1].contains(&CalVerToken::Micro) {
+            return Err(invalid_format("'MICRO' must be the last segment"));
+        }
+
+        Ok(Self {
+            tokens,
+            date: Utc::now().naive_utc().date(),
+        })
+    }
+
+    /// Sets the release date
+    pub fn with_date(mut self, date: NaiveDate) -> Self {
+        self.date = date;
+        self
+    }
+
+    /// Returns the segments of a version
+    fn segments(version: &Version) -> [u64; 3] {
+        [version.major, version.minor, version.patch]
+    }
+}
+
+impl VersionScheme for CalVer {
+    fn parse(&self, s: &str) -> Option<Version> {
+        let (core, pre) = match s.split_once('-') {
+            Some((core, pre)) => (core, Prerelease::new(pre).ok()?),
+            None => (s, Prerelease::EMPTY),
+        };
+
+        let segments: Vec<&str> = core.split('.').collect();
+        if segments.len() != self.tokens.len() {
+            return None;
+        }
+        let mut values = [0_u64; 3];
+        for (i, (token, segment)) in self.tokens.iter().zip(segments).enumerate() {
+            values[i] = token.parse_segment(segment)?;
+        }
+
+        let mut version = Version::new(values[0], values[1], values[2]);
+        version.pre = pre;
+        Some(version)
+    }
+
+    fn format(&self, version: &Version) -> String {
+        let core = self
+            .tokens
+            .iter()
+            .zip(Self::segments(version))
+            .map(|(token, value)| token.format_segment(value))
+            .collect::<Vec<_>>()
+            .join(".");
+        if version.pre.is_empty() {
+            core
+        } else {
+            format!("{core}-{}", version.pre)
+        }
+    }
+
+    fn next_version(&self, curr: Option<&Version>, _bump: Bump) -> Result<Version> {
+        let mut values = [0_u64; 3];
+        for (i, token) in self.tokens.iter().enumerate() {
+            values[i] = match token.value(self.date) {
+                Some(value) => value,
+                None => {
+                    // NB: the micro number is incremented within the same period
+                    match curr.map(Self::segments) {
+                        Some(curr) if curr[..i] == values[..i] => curr[i] + 1,
+                        _ => 0,
+                    }
+                }
+            };
+        }
+
+        let next = Version::new(values[0], values[1], values[2]);
+        if curr.map(Self::segments) == Some(values) {
+            return Err(Error::InternalError(format!(
+                "Version {} is already released",
+                self.format(&next)
+            )));
+        }
+        Ok(next)
+    }
+}
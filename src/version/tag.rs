@@ -0,0 +1,102 @@
+//! Tag patterns
+//!
+//! Version tags follow a pattern with a `{version}` placeholder, and an optional `{package}` placeholder
+//! (eg. `v{version}`, `release-{version}` or `{package}@{version}`).
+
+use regex::Regex;
+
+use crate::{
+    config::Config,
+    error::{Error, Result},
+};
+
+/// Default tag pattern
+///
+/// NB: the default pattern also matches the tags without the `v` prefix (eg. `1.2.0`).
+pub const DEFAULT_TAG_PATTERN: &str = "v{version}";
+
+/// Version placeholder
+const VERSION_PLACEHOLDER: &str = "{version}";
+
+/// Package placeholder
+const PACKAGE_PLACEHOLDER: &str = "{package}";
+
+/// Tag pattern (eg. `v{version}`)
+#[derive(Debug, Clone)]
+pub struct TagPattern {
+    /// Pattern
+    pattern: String,
+    /// Package name, for the `{package}` placeholder
+    package: Option<String>,
+    /// Regex matching the tags, with a `version` group
+    regex: Regex,
+}
+
+impl TagPattern {
+    /// Creates a new tag pattern, with the package name if the pattern has a `{package}` placeholder
+    pub fn new(pattern: &str, package: Option<&str>) -> Result<Self> {
+        let invalid_pattern =
+            |msg: &str| Error::InternalError(format!("Invalid tag pattern '{pattern}': {msg}"));
+
+        if pattern.matches(VERSION_PLACEHOLDER).count() != 1 {
+            return Err(invalid_pattern("expected a single '{version}' placeholder"));
+        }
+        let package = match package {
+            Some(package) => package,
+            None if pattern.contains(PACKAGE_PLACEHOLDER) => {
+                return Err(invalid_pattern("the package name is not set"))
+            }
+            None => "",
+        };
+
+        let regex = pattern
+            .split(VERSION_PLACEHOLDER)
+            .map(|s| regex::escape(&s.replace(PACKAGE_PLACEHOLDER, package)))
+            .collect::<Vec<_>>()
+            .join("(?P<version>.+)");
+        let regex =
+            Regex::new(&format!("^{regex}$")).map_err(|err| invalid_pattern(&err.to_string()))?;
+
+        Ok(Self {
+            pattern: pattern.to_string(),
+            package: (!package.is_empty()).then(|| package.to_string()),
+            regex,
+        })
+    }
+
+    /// Returns the default tag pattern, matching the tags with or without the `v` prefix
+    /// (eg. `v1.2.0` or `1.2.0`), and formatting the tags with the prefix
+    pub fn default_pattern() -> Self {
+        Self {
+            pattern: DEFAULT_TAG_PATTERN.to_string(),
+            package: None,
+            regex: Regex::new("^v?(?P<version>.+)$").expect("Invalid regex"),
+        }
+    }
+
+    /// Returns the version part of a tag, or `None` if the tag does not match the pattern
+    pub fn parse<'a>(&self, tag: &'a str) -> Option<&'a str> {
+        self.regex
+            .captures(tag)
+            .and_then(|capts| capts.name("version"))
+            .map(|m| m.as_str())
+    }
+
+    /// Formats the tag of a version
+    pub fn format(&self, version: &str) -> String {
+        self.pattern
+            .replace(
+                PACKAGE_PLACEHOLDER,
+                self.package.as_deref().unwrap_or_default(),
+            )
+            .replace(VERSION_PLACEHOLDER, version)
+    }
+}
+
+/// Returns the tag pattern of the repo (cf. the `[release] tag_pattern` config)
+pub fn tag_pattern(config: &Config) -> Result<TagPattern> {
+    match &config.release.tag_pattern {
+        Some(pattern) => TagPattern::new(pattern, config.release.package.as_deref()),
+        None => Ok(TagPattern::default_pattern()),
+    }
+}
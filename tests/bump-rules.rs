@@ -0,0 +1,148 @@
+//! Testing of the version increment rules
+
+use gitext::{
+    config::{Bump, Config},
+    conventional::ConventionalCommitMessage,
+    error::Error,
+    version::get_commit_bump,
+};
+use indoc::indoc;
+
+fn commit_bump(config: &Config, msg: &str) -> Bump {
+    let commit = ConventionalCommitMessage::parse_with_config(msg, config).unwrap();
+    get_commit_bump(config, &commit)
+}
+
+#[test]
+fn default_rules() {
+    let config = Config::default();
+
+    assert_eq!(commit_bump(&config, "feat: add endpoint"), Bump::Minor);
+    assert_eq!(commit_bump(&config, "fix: handle errors"), Bump::Patch);
+    assert_eq!(commit_bump(&config, "docs: update readme"), Bump::None);
+    assert_eq!(commit_bump(&config, "ci: add workflow"), Bump::None);
+    assert_eq!(commit_bump(&config, "test: add tests"), Bump::None);
+    assert_eq!(commit_bump(&config, "docs!: drop old guide"), Bump::Major);
+}
+
+#[test]
+fn config_rules() {
+    let s = indoc!(
+        r#"
+        types_inc_minor = ["feat", "deps"]
+
+        [types]
+        feat = "New features"
+        docs = "Documentation"
+        deps = "Dependencies"
+
+        [bump]
+        feat = "minor"
+        docs = "none"
+        "docs(api)" = "minor"
+        "#
+    );
+    let config = Config {
+        commit: toml::from_str(s).unwrap(),
+        ..Default::default()
+    };
+
+    assert_eq!(commit_bump(&config, "docs: update readme"), Bump::None);
+    assert_eq!(
+        commit_bump(&config, "docs(api): document endpoint"),
+        Bump::Minor
+    );
+    assert_eq!(commit_bump(&config, "docs(ui,api): document"), Bump::Minor);
+    assert_eq!(commit_bump(&config, "docs(ui): document"), Bump::None);
+
+    // NB: types without rule fall back to `types_inc_minor`
+    assert_eq!(commit_bump(&config, "deps: bump serde"), Bump::Minor);
+    assert_eq!(config.type_bump("other", &[]), Bump::Patch);
+}
+
+#[test]
+fn default_rules_fallback() {
+    // NB: without `[bump]`, `types_inc_minor` applies, then the built-in increments
+    let s = indoc!(
+        r#"
+        types_inc_minor = ["feat", "perf"]
+
+        [types]
+        feat = "New features"
+        perf = "Performance"
+        docs = "Documentation"
+        "#
+    );
+    let config = Config {
+        commit: toml::from_str(s).unwrap(),
+        ..Default::default()
+    };
+    assert!(config.commit.bump.is_empty());
+    assert_eq!(commit_bump(&config, "perf: faster parsing"), Bump::Minor);
+    assert_eq!(commit_bump(&config, "docs: update readme"), Bump::None);
+
+    // NB: a single rule does not drop the built-in increments
+    let s = indoc!(
+        r#"
+        types_inc_minor = ["feat"]
+
+        [types]
+        fix = "Bug fixes"
+        docs = "Documentation"
+        ci = "Continuous Integration"
+
+        [bump]
+        fix = "minor"
+        "#
+    );
+    let config = Config {
+        commit: toml::from_str(s).unwrap(),
+        ..Default::default()
+    };
+    assert_eq!(commit_bump(&config, "fix: handle errors"), Bump::Minor);
+    assert_eq!(commit_bump(&config, "docs: update readme"), Bump::None);
+    assert_eq!(commit_bump(&config, "ci: add workflow"), Bump::None);
+}
+
+#[test]
+fn history_lint_rules() {
+    let lint = indoc!(
+        r#"
+        subject_full_stop = "error"
+
+        [header_max_length]
+        level = "error"
+        value = 20
+
+        [scope_enum]
+        level = "error"
+        value = ["ui"]
+        "#
+    );
+    let config = Config {
+        lint: toml::from_str(lint).unwrap(),
+        ..Default::default()
+    };
+    let msg = "feat(api)!: drop the old endpoints.";
+
+    let (_, diagnostics) = ConventionalCommitMessage::lint(msg, &config);
+    let rules: Vec<_> = diagnostics.iter().map(|d| d.rule.as_str()).collect();
+    assert_eq!(
+        rules,
+        vec!["header-max-length", "subject-full-stop", "scope-enum"]
+    );
+
+    // NB: the lint rules do not drop the commits of the history
+    let commit = ConventionalCommitMessage::parse_log_message(msg, &config)
+        .unwrap()
+        .unwrap();
+    assert_eq!(get_commit_bump(&config, &commit), Bump::Major);
+    assert!(ConventionalCommitMessage::parse_log_message("feat api: add", &config).is_err());
+}
+
+#[test]
+fn no_release_errors() {
+    assert!(Error::NoReleaseNeeded("no bump".to_string()).is_no_release());
+    assert!(Error::NoCommits("no commits".to_string()).is_no_release());
+    assert!(!Error::InternalError("failure".to_string()).is_no_release());
+}
@@ -1,10 +1,110 @@
-use std::env;
+mod common;
 
-use gitext::{commands::shared::load_config, version::exec_bump_commands};
+use std::{env, fs};
+
+use gitext::{
+    commands::shared::load_config,
+    config::Config,
+    version::{exec_bump_commands, BumpContext},
+};
+use indoc::indoc;
+use tempfile::TempDir;
+
+use common::temp_dir;
 
 #[test]
 fn test_exec_bump_commands() {
     let config = load_config(&env::current_dir().unwrap(), false);
-    let commands = exec_bump_commands(&config, "0.1.0").unwrap();
+    let context = BumpContext::new(&config, "0.1.0", None).unwrap();
+    let commands = exec_bump_commands(&config, &context).unwrap();
     eprintln!("{:?}", commands);
 }
+
+/// Returns a config with bump commands, in a test directory (removed when dropped)
+fn test_config(release: &str) -> (TempDir, Config) {
+    let dir = temp_dir(&[]);
+    fs::create_dir_all(dir.path().join("sub")).unwrap();
+    let config = Config {
+        root_dir: dir.path().to_path_buf(),
+        release: toml::from_str(release).unwrap(),
+        ..Default::default()
+    };
+    (dir, config)
+}
+
+fn read(config: &Config, path: &str) -> String {
+    fs::read_to_string(config.root_dir.join(path)).unwrap()
+}
+
+#[test]
+fn test_bump_commands_context() {
+    let (_dir, config) = test_config(indoc!(
+        r#"
+            bump_commands = [
+                "touch 'file {{major}}.{{minor}}.{{patch}}.txt'",
+                { cmd = "echo {{previous_version}} {{version}} {{tag}} > out.txt", shell = true },
+                { cmd = "sh -c 'echo $NAME > env.txt'", cwd = "sub", env = { NAME = "<{{version}}>" } },
+            ]
+            "#
+    ));
+    let context = BumpContext::new(&config, "1.3.0-beta.1", Some("1.2.0")).unwrap();
+    assert_eq!((context.major, context.minor, context.patch), (1, 3, 0));
+
+    let commands = exec_bump_commands(&config, &context).unwrap();
+    assert_eq!(commands[0], "touch 'file 1.3.0.txt'");
+    assert!(config.root_dir.join("file 1.3.0.txt").exists());
+    assert_eq!(
+        read(&config, "out.txt"),
+        "1.2.0 1.3.0-beta.1 v1.3.0-beta.1\n"
+    );
+    assert_eq!(read(&config, "sub/env.txt"), "<1.3.0-beta.1>\n");
+}
+
+#[test]
+fn test_bump_commands_errors() {
+    let context = |config: &Config| BumpContext::new(config, "1.3.0", None).unwrap();
+
+    let (_dir, config) = test_config(r#"bump_commands = ["false"]"#);
+    assert!(exec_bump_commands(&config, &context(&config)).is_err());
+
+    let (_dir, config) = test_config(r#"bump_commands = ["echo 'unclosed"]"#);
+    assert!(exec_bump_commands(&config, &context(&config)).is_err());
+
+    let (_dir, config) = test_config(r#"bump_commands = [{ cmd = "sleep 5", timeout = 1 }]"#);
+    let err = exec_bump_commands(&config, &context(&config)).unwrap_err();
+    assert!(err.to_string().contains("timed out"), "{err}");
+
+    let (_dir, config) = test_config(r#"bump_commands = [{ cmd = "true", cwd = "none" }]"#);
+    assert!(exec_bump_commands(&config, &context(&config)).is_err());
+}
+
+#[test]
+fn test_bump_commands_save() {
+    let (_dir, config) = test_config(indoc!(
+        r#"
+            bump_commands = [
+                "cargo update",
+                { cmd = "npm version {{version}}", cwd = "web", env = { CI = "1" }, timeout = 60 },
+            ]
+
+            [channels]
+            next = "beta"
+            "#
+    ));
+    // NB: the mixed commands are saved as tables
+    config.save(&config.root_dir).unwrap();
+    let loaded = Config::load(&config.root_dir).unwrap().unwrap();
+    let commands: Vec<_> = loaded
+        .release
+        .bump_commands
+        .iter()
+        .map(|c| c.config())
+        .collect();
+    assert_eq!(commands[0].cmd, "cargo update");
+    assert_eq!(commands[0].cwd, None);
+    assert_eq!(commands[1].cmd, "npm version {{version}}");
+    assert_eq!(commands[1].cwd, Some("web".into()));
+    assert_eq!(commands[1].env.get("CI").map(|v| v.as_str()), Some("1"));
+    assert_eq!(commands[1].timeout, Some(60));
+    assert_eq!(loaded.release.channels.get("next").unwrap(), "beta");
+}
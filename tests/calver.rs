@@ -0,0 +1,102 @@
+//! Testing of the version schemes
+
+use chrono::NaiveDate;
+use gitext::{
+    config::{Bump, PreStablePolicy},
+    version::scheme::{CalVer, SemVer, VersionScheme},
+};
+use semver::Version;
+
+#[test]
+fn calver_parse_format() {
+    let scheme = CalVer::new("YYYY.0M.MICRO").unwrap();
+
+    let version = scheme.parse("2024.05.2").unwrap();
+    assert_eq!(version, Version::new(2024, 5, 2));
+    assert_eq!(scheme.format(&version), "2024.05.2");
+
+    let version = scheme.parse("2024.05.0-beta.1").unwrap();
+    assert_eq!(version, Version::parse("2024.5.0-beta.1").unwrap());
+    assert_eq!(scheme.format(&version), "2024.05.0-beta.1");
+
+    // invalid segments
+    assert!(scheme.parse("2024.5.2").is_none());
+    assert!(scheme.parse("2024.13.0").is_none());
+    assert!(scheme.parse("2024.05.02").is_none());
+    assert!(scheme.parse("2024.05").is_none());
+    assert!(scheme.parse("1.2.3").is_none());
+
+    let scheme = CalVer::new("YY.MM.MICRO").unwrap();
+    assert_eq!(scheme.parse("24.5.0"), Some(Version::new(24, 5, 0)));
+    assert!(scheme.parse("24.05.0").is_none());
+
+    // invalid formats
+    assert!(CalVer::new("YYYY.MICRO.MM").is_err());
+    assert!(CalVer::new("YYYY.0M.DD.MICRO").is_err());
+    assert!(CalVer::new("YYYY.QQ").is_err());
+}
+
+#[test]
+fn calver_sort() {
+    let scheme = CalVer::new("YYYY.0M.MICRO").unwrap();
+
+    let mut versions: Vec<_> = ["2024.10.0", "2024.05.1", "2023.12.3", "2024.05.0"]
+        .iter()
+        .filter_map(|v| scheme.parse(v))
+        .collect();
+    versions.sort();
+    let versions: Vec<_> = versions.iter().map(|v| scheme.format(v)).collect();
+    assert_eq!(
+        versions,
+        vec!["2023.12.3", "2024.05.0", "2024.05.1", "2024.10.0"]
+    );
+}
+
+#[test]
+fn calver_next_version() {
+    let scheme = CalVer::new("YYYY.0M.MICRO")
+        .unwrap()
+        .with_date(NaiveDate::from_ymd(2024, 5, 17));
+
+    // 1st release
+    let next = scheme.next_version(None, Bump::Patch).unwrap();
+    assert_eq!(scheme.format(&next), "2024.05.0");
+
+    // same month
+    let curr = scheme.parse("2024.05.1").unwrap();
+    let next = scheme.next_version(Some(&curr), Bump::Major).unwrap();
+    assert_eq!(scheme.format(&next), "2024.05.2");
+
+    // new month
+    let curr = scheme.parse("2024.04.3").unwrap();
+    let next = scheme.next_version(Some(&curr), Bump::Patch).unwrap();
+    assert_eq!(scheme.format(&next), "2024.05.0");
+
+    // without micro, a single release per period
+    let scheme = CalVer::new("YYYY.0M.0D")
+        .unwrap()
+        .with_date(NaiveDate::from_ymd(2024, 5, 7));
+    let curr = scheme.parse("2024.05.07").unwrap();
+    assert!(scheme.next_version(Some(&curr), Bump::Patch).is_err());
+    let curr = scheme.parse("2024.05.06").unwrap();
+    let next = scheme.next_version(Some(&curr), Bump::Patch).unwrap();
+    assert_eq!(scheme.format(&next), "2024.05.07");
+}
+
+#[test]
+fn semver_scheme() {
+    let scheme = SemVer::new(PreStablePolicy::default());
+
+    assert_eq!(scheme.parse("1.2.3"), Some(Version::new(1, 2, 3)));
+    assert!(scheme.parse("2024.05").is_none());
+    assert_eq!(
+        scheme.next_version(None, Bump::Minor).unwrap(),
+        Version::new(0, 0, 1)
+    );
+    assert_eq!(
+        scheme
+            .next_version(Some(&Version::new(1, 2, 3)), Bump::Minor)
+            .unwrap(),
+        Version::new(1, 3, 0)
+    );
+}
@@ -0,0 +1,50 @@
+//! Testing of the changelog generation, on a test repo
+
+mod common;
+
+use std::process::Command;
+
+use gitext::config::Config;
+
+use common::{git, temp_repo};
+
+#[test]
+fn changelog_type_aliases() {
+    let repo = temp_repo();
+    let dir = repo.path();
+
+    // NB: the types are listed with an alias, in the order of the groups
+    let mut config = Config::default();
+    config.changelog.types = vec!["fix".to_string(), "feature".to_string()];
+    config.save(dir).unwrap();
+    git(
+        dir,
+        &["remote", "add", "origin", "https://github.com/org/repo.git"],
+    );
+    git(dir, &["add", "-A"]);
+    git(dir, &["commit", "-q", "-m", "feat: first"]);
+    git(dir, &["tag", "-a", "v1.0.0", "-m", "v1.0.0"]);
+    git(
+        dir,
+        &["commit", "-q", "--allow-empty", "-m", "feat: add endpoint"],
+    );
+    git(
+        dir,
+        &["commit", "-q", "--allow-empty", "-m", "docs: update readme"],
+    );
+    git(
+        dir,
+        &["commit", "-q", "--allow-empty", "-m", "fix: handle errors"],
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_git-changelog"))
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let changelog = String::from_utf8_lossy(&output.stdout);
+    let position = |s: &str| changelog.find(s).unwrap_or_else(|| panic!("{s}"));
+    assert!(position("### Bug fixes") < position("### New features"));
+    assert!(position("Handle errors") < position("Add endpoint"));
+    assert!(!changelog.contains("Update readme"));
+}
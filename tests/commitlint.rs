@@ -0,0 +1,87 @@
+//! Testing of the commitlint config import
+
+use gitext::{
+    config::{commitlint::CommitlintConfig, Config},
+    lint::{Case, LintLevel, LintRule},
+};
+use indoc::indoc;
+
+#[test]
+fn json_config() {
+    let s = indoc!(
+        r#"
+        {
+          "rules": {
+            "type-enum": [2, "always", ["feat", "fix", "deps"]],
+            "scope-enum": [1, "always", ["api", "ui"]],
+            "scope-empty": [2, "never"],
+            "header-max-length": [2, "always", 80],
+            "subject-case": [2, "always", "sentence-case"],
+            "footer-max-line-length": [2, "always", 100]
+          }
+        }
+        "#
+    );
+
+    let mut config = Config::default();
+    let ignored = CommitlintConfig::parse(s).unwrap().apply(&mut config);
+
+    assert_eq!(ignored, vec!["footer-max-line-length"]);
+    assert_eq!(config.valid_commit_types(), vec!["deps", "feat", "fix"]);
+    assert_eq!(config.commit.types["feat"], "New features");
+    assert_eq!(config.commit.types["deps"], "deps");
+    assert_eq!(config.changelog.types, vec!["feat", "fix"]);
+    assert_eq!(
+        config.lint.scope_enum,
+        LintRule::new(LintLevel::Warn, vec!["api".to_string(), "ui".to_string()])
+    );
+    assert_eq!(config.lint.scope_required, LintLevel::Error);
+    assert_eq!(
+        config.lint.header_max_length,
+        LintRule::new(LintLevel::Error, 80)
+    );
+    assert_eq!(
+        config.lint.subject_case,
+        LintRule::new(LintLevel::Error, Case::SentenceCase)
+    );
+}
+
+#[test]
+fn yaml_config_conventional() {
+    let s = indoc!(
+        r#"
+        extends:
+          - "@commitlint/config-conventional"
+        rules:
+          header-max-length: [0, always, 100]
+          body-empty: [1, never]
+        "#
+    );
+
+    let mut config = Config::default();
+    CommitlintConfig::parse(s).unwrap().apply(&mut config);
+
+    assert!(config.valid_commit_types().contains(&"revert".to_string()));
+    assert!(!config.valid_commit_types().contains(&"cd".to_string()));
+    assert_eq!(config.lint.header_max_length.level, LintLevel::Off);
+    assert_eq!(
+        config.lint.body_max_line_length,
+        LintRule::new(LintLevel::Error, 100)
+    );
+    assert_eq!(config.lint.subject_full_stop, LintLevel::Error);
+    assert_eq!(config.lint.body_leading_blank, LintLevel::Warn);
+    assert_eq!(
+        config.lint.subject_case,
+        LintRule::new(LintLevel::Error, Case::LowerFirst)
+    );
+    assert_eq!(config.lint.body_required_types.level, LintLevel::Warn);
+    assert_eq!(
+        config.lint.body_required_types.value,
+        config.valid_commit_types()
+    );
+}
+
+#[test]
+fn invalid_config() {
+    assert!(CommitlintConfig::parse("rules: [").is_err());
+}
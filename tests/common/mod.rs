@@ -0,0 +1,45 @@
+//! Fixtures shared by the integration tests
+
+#![allow(dead_code)]
+
+use std::{fs, path::Path, process::Command};
+
+use tempfile::TempDir;
+
+/// Creates a temporary directory with files (removed when dropped)
+pub fn temp_dir(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (path, content) in files {
+        let path = dir.path().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+/// Creates a git repo in a temporary directory (removed when dropped)
+pub fn temp_repo() -> TempDir {
+    let dir = temp_dir(&[]);
+    git(dir.path(), &["init", "-q", "-b", "main"]);
+    git(dir.path(), &["config", "commit.gpgsign", "false"]);
+    git(dir.path(), &["config", "tag.gpgsign", "false"]);
+    dir
+}
+
+/// Runs a git command in a directory
+///
+/// NB: the author and committer are fixed, so are the dates
+pub fn git(dir: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .env("GIT_AUTHOR_NAME", "Alice")
+        .env("GIT_AUTHOR_EMAIL", "alice@example.com")
+        .env("GIT_AUTHOR_DATE", "2022-01-02T03:04:05+02:00")
+        .env("GIT_COMMITTER_NAME", "Bob")
+        .env("GIT_COMMITTER_EMAIL", "bob@example.com")
+        .env("GIT_COMMITTER_DATE", "2022-01-03T00:00:00+00:00")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+}
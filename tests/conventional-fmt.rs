@@ -1,6 +1,9 @@
 //! Testing of conventional commits
 
-use gitext::conventional::ConventionalCommitMessage;
+use gitext::conventional::{
+    issue::{IssueAction, IssueId, IssueRef},
+    ConventionalCommitMessage, FooterItem, Trailer,
+};
 use indoc::indoc;
 
 #[test]
@@ -11,7 +14,8 @@ fn oneliner_no_scope() {
         subject: "commit subject".to_string(),
         body: None,
         breaking_change: None,
-        closed_issues: None,
+        footer: vec![],
+        gitmoji: None,
     };
 
     let msg = indoc!("fix: commit subject");
@@ -27,7 +31,8 @@ fn oneliner_scope() {
         subject: "commit subject".to_string(),
         body: None,
         breaking_change: None,
-        closed_issues: None,
+        footer: vec![],
+        gitmoji: None,
     };
 
     let msg = indoc!("fix(myscope): commit subject");
@@ -43,7 +48,8 @@ fn oneliner_scope_breaking_change() {
         subject: "commit subject".to_string(),
         body: None,
         breaking_change: Some("breaking change".to_string()),
-        closed_issues: None,
+        footer: vec![],
+        gitmoji: None,
     };
 
     let msg = indoc!(
@@ -63,7 +69,8 @@ fn body() {
         subject: "commit subject".to_string(),
         body: Some("Commit body\nAnother body line".to_string()),
         breaking_change: None,
-        closed_issues: None,
+        footer: vec![],
+        gitmoji: None,
     };
 
     let msg = indoc!(
@@ -84,7 +91,11 @@ fn body_with_issues() {
         subject: "commit subject".to_string(),
         body: Some("Commit body\nAnother body line".to_string()),
         breaking_change: None,
-        closed_issues: Some(vec![1, 2]),
+        footer: vec![
+            FooterItem::Issues(vec![IssueRef::new(IssueAction::Closes, IssueId::Issue(1))]),
+            FooterItem::Issues(vec![IssueRef::new(IssueAction::Closes, IssueId::Issue(2))]),
+        ],
+        gitmoji: None,
     };
 
     let msg = indoc!(
@@ -108,7 +119,11 @@ fn body_with_change_and_issues() {
         subject: "commit subject".to_string(),
         body: Some("Commit body\nAnother body line".to_string()),
         breaking_change: Some("A breaking change".to_string()),
-        closed_issues: Some(vec![1, 2]),
+        footer: vec![
+            FooterItem::Issues(vec![IssueRef::new(IssueAction::Closes, IssueId::Issue(1))]),
+            FooterItem::Issues(vec![IssueRef::new(IssueAction::Closes, IssueId::Issue(2))]),
+        ],
+        gitmoji: None,
     };
 
     let msg = indoc!(
@@ -124,3 +139,32 @@ fn body_with_change_and_issues() {
 
     assert_eq!(commit.to_string(), msg);
 }
+
+#[test]
+fn body_with_trailers() {
+    let commit = ConventionalCommitMessage {
+        r#type: "fix".to_string(),
+        scope: None,
+        subject: "commit subject".to_string(),
+        body: Some("Commit body".to_string()),
+        breaking_change: None,
+        footer: vec![
+            FooterItem::Issues(vec![IssueRef::new(IssueAction::Closes, IssueId::Issue(1))]),
+            FooterItem::Trailer(Trailer::new("Refs", "abc123")),
+            FooterItem::Trailer(Trailer::new("Signed-off-by", "John Doe <john@doe.com>")),
+        ],
+        gitmoji: None,
+    };
+
+    let msg = indoc!(
+        "fix: commit subject
+    
+    Commit body
+    
+    Closes #1
+    Refs: abc123
+    Signed-off-by: John Doe <john@doe.com>"
+    );
+
+    assert_eq!(commit.to_string(), msg);
+}
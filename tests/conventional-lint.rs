@@ -0,0 +1,270 @@
+//! Testing of conventional commits lint diagnostics
+
+use gitext::{
+    config::{Config, LintConfig},
+    conventional::ConventionalCommitMessage,
+    lint::{Case, LintLevel, LintRule, Severity, Span},
+};
+use indoc::indoc;
+
+#[test]
+fn valid_commit() {
+    let msg = indoc!(
+        "fix(myscope): commit subject
+
+    commit body"
+    );
+
+    let (commit, diagnostics) = ConventionalCommitMessage::parse_with_diagnostics(
+        msg,
+        &Config::default().valid_commit_types(),
+    );
+
+    assert!(commit.is_some());
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn all_diagnostics() {
+    let msg = indoc!(
+        "Fix(Scope): Commit subject
+    commit body"
+    );
+
+    let (commit, diagnostics) = ConventionalCommitMessage::parse_with_diagnostics(
+        msg,
+        &Config::default().valid_commit_types(),
+    );
+
+    assert!(commit.is_some());
+    let rules: Vec<_> = diagnostics.iter().map(|d| d.rule.as_str()).collect();
+    assert_eq!(
+        rules,
+        vec![
+            "type-case",
+            "scope-case",
+            "subject-case",
+            "body-leading-blank"
+        ]
+    );
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+
+    assert_eq!(diagnostics[0].span, Span::new(1, 1, 3));
+    assert_eq!(diagnostics[1].span, Span::new(1, 5, 5));
+    assert_eq!(diagnostics[2].span, Span::new(1, 13, 1));
+    assert_eq!(diagnostics[3].span, Span::new(2, 1, 0));
+    assert_eq!(
+        diagnostics[2]
+            .suggestion
+            .as_ref()
+            .and_then(|s| s.replacement.as_deref()),
+        Some("c")
+    );
+}
+
+#[test]
+fn missing_separator() {
+    let msg = indoc!("fix commit subject");
+
+    let (commit, diagnostics) = ConventionalCommitMessage::parse_with_diagnostics(
+        msg,
+        &Config::default().valid_commit_types(),
+    );
+
+    assert!(commit.is_none());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, "header-format");
+    assert!(
+        ConventionalCommitMessage::parse(msg, &Config::default().valid_commit_types()).is_err()
+    );
+}
+
+#[test]
+fn footer_diagnostics() {
+    let msg = indoc!(
+        "fix: commit subject
+
+    BREAKING CHANGE: a breaking change
+    BREAKING CHANGE: another breaking change
+    Closes #abc"
+    );
+
+    let (_commit, diagnostics) = ConventionalCommitMessage::parse_with_diagnostics(
+        msg,
+        &Config::default().valid_commit_types(),
+    );
+
+    let rules: Vec<_> = diagnostics
+        .iter()
+        .map(|d| (d.rule.as_str(), d.span.line))
+        .collect();
+    assert_eq!(
+        rules,
+        vec![("footer-breaking-change", 4), ("footer-issue", 5)]
+    );
+}
+
+#[test]
+fn render_snippet() {
+    let msg = indoc!("fix: Commit subject");
+
+    let (_commit, diagnostics) = ConventionalCommitMessage::parse_with_diagnostics(
+        msg,
+        &Config::default().valid_commit_types(),
+    );
+
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "error[subject-case]: subject must start with lowercase"
+    );
+    assert_eq!(
+        diagnostics[0].render_snippet(msg),
+        indoc!(
+            " --> 1:6
+              |
+            1 | fix: Commit subject
+              |      ^
+              = help: use 'commit subject'"
+        )
+    );
+}
+
+#[test]
+fn configured_rules() {
+    let msg = indoc!(
+        "feat: Commit subject which is far too long for the configured limit.
+
+    commit body line which is also too long"
+    );
+
+    let rules = LintConfig {
+        subject_case: LintRule::new(LintLevel::Off, Case::LowerFirst),
+        subject_full_stop: LintLevel::Warn,
+        scope_required: LintLevel::Error,
+        header_max_length: LintRule::new(LintLevel::Error, 50),
+        body_max_line_length: LintRule::new(LintLevel::Warn, 30),
+        footer_required_types: LintRule::new(LintLevel::Error, vec!["feat".to_string()]),
+        ..Default::default()
+    };
+
+    let (commit, diagnostics) = ConventionalCommitMessage::parse_with_rules(
+        msg,
+        &Config::default().valid_commit_types(),
+        &rules,
+    );
+
+    assert!(commit.is_some());
+    let rules: Vec<_> = diagnostics
+        .iter()
+        .map(|d| (d.rule.as_str(), d.severity))
+        .collect();
+    assert_eq!(
+        rules,
+        vec![
+            ("header-max-length", Severity::Error),
+            ("subject-full-stop", Severity::Warning),
+            ("scope-required", Severity::Error),
+            ("body-max-line-length", Severity::Warning),
+            ("footer-required", Severity::Error),
+        ]
+    );
+    assert_eq!(diagnostics[0].span, Span::new(1, 51, 18));
+    assert_eq!(diagnostics[1].span, Span::new(1, 68, 1));
+}
+
+#[test]
+fn scope_enum() {
+    let rules = LintConfig {
+        scope_enum: LintRule::new(LintLevel::Error, vec!["api".to_string(), "ui".to_string()]),
+        ..Default::default()
+    };
+
+    let (_, diagnostics) = ConventionalCommitMessage::parse_with_rules(
+        "feat(ui): commit subject",
+        &Config::default().valid_commit_types(),
+        &rules,
+    );
+    assert!(diagnostics.is_empty());
+
+    let (_, diagnostics) = ConventionalCommitMessage::parse_with_rules(
+        "feat(db): commit subject",
+        &Config::default().valid_commit_types(),
+        &rules,
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, "scope-enum");
+    assert_eq!(diagnostics[0].span, Span::new(1, 6, 2));
+
+    // NB: each scope is checked
+    let (_, diagnostics) = ConventionalCommitMessage::parse_with_rules(
+        "feat(ui, db): commit subject",
+        &Config::default().valid_commit_types(),
+        &rules,
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "scope (db) is not allowed");
+    assert_eq!(diagnostics[0].span, Span::new(1, 10, 2));
+}
+
+#[test]
+fn case_rules() {
+    let rules = LintConfig {
+        subject_case: LintRule::new(LintLevel::Error, Case::SentenceCase),
+        scope_case: LintRule::new(LintLevel::Warn, Case::UpperCase),
+        ..Default::default()
+    };
+
+    let (_, diagnostics) = ConventionalCommitMessage::parse_with_rules(
+        "feat(Api): commit subject",
+        &Config::default().valid_commit_types(),
+        &rules,
+    );
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].rule, "scope-case");
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(diagnostics[1].rule, "subject-case");
+    assert_eq!(diagnostics[1].message, "subject must start with uppercase");
+
+    // multiple scopes
+    let (_, diagnostics) = ConventionalCommitMessage::parse_with_diagnostics(
+        "feat(ui, Api): commit subject",
+        &Config::default().valid_commit_types(),
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, "scope-case");
+    assert_eq!(
+        diagnostics[0].suggestion.as_ref().unwrap().replacement,
+        Some("ui, api".to_string())
+    );
+}
+
+#[test]
+fn lint_config_toml() {
+    // default config roundtrip
+    let config_str = toml::to_string(&Config::default()).unwrap();
+    let config = toml::from_str::<Config>(&config_str).unwrap();
+    assert_eq!(config.lint.subject_case, LintConfig::default().subject_case);
+
+    // partial lint config
+    let lint_str = indoc!(
+        r#"
+        subject_full_stop = "error"
+
+        [header_max_length]
+        level = "warn"
+        value = 100
+
+        [scope_enum]
+        level = "error"
+        value = ["api", "ui"]
+        "#
+    );
+    let lint = toml::from_str::<LintConfig>(lint_str).unwrap();
+
+    assert_eq!(lint.subject_full_stop, LintLevel::Error);
+    assert_eq!(lint.header_max_length, LintRule::new(LintLevel::Warn, 100));
+    assert_eq!(lint.scope_enum.value, vec!["api", "ui"]);
+    // NB: missing rules use the default
+    assert_eq!(lint.body_leading_blank, LintLevel::Error);
+}
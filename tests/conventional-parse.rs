@@ -1,6 +1,12 @@
 //! Testing of conventional commits parsing
 
-use gitext::{config::Config, conventional::ConventionalCommitMessage};
+use gitext::{
+    config::Config,
+    conventional::{
+        issue::{IssueAction, IssueId, IssueRef},
+        ConventionalCommitMessage, FooterItem, Trailer, TrailerSeparator,
+    },
+};
 use indoc::indoc;
 
 #[test]
@@ -18,7 +24,8 @@ fn oneliner_simple() {
             subject: "commit subject".to_string(),
             body: None,
             breaking_change: None,
-            closed_issues: None
+            footer: vec![],
+            gitmoji: None,
         }
     )
 }
@@ -38,7 +45,8 @@ fn oneliner_scope() {
             subject: "commit subject".to_string(),
             body: None,
             breaking_change: None,
-            closed_issues: None
+            footer: vec![],
+            gitmoji: None,
         }
     )
 }
@@ -58,7 +66,8 @@ fn oneliner_breaking() {
             subject: "commit subject".to_string(),
             body: None,
             breaking_change: Some("".to_string()),
-            closed_issues: None
+            footer: vec![],
+            gitmoji: None,
         }
     )
 }
@@ -78,7 +87,8 @@ fn oneliner_scope_breaking() {
             subject: "commit subject".to_string(),
             body: None,
             breaking_change: Some("".to_string()),
-            closed_issues: None
+            footer: vec![],
+            gitmoji: None,
         }
     )
 }
@@ -102,7 +112,8 @@ fn body_simple() {
             subject: "commit subject".to_string(),
             body: Some("commit body".to_string()),
             breaking_change: None,
-            closed_issues: None
+            footer: vec![],
+            gitmoji: None,
         }
     )
 }
@@ -127,7 +138,8 @@ fn body_multiline_simple() {
             subject: "commit subject".to_string(),
             body: Some("commit body\ncommit body line 2".to_string()),
             breaking_change: None,
-            closed_issues: None
+            footer: vec![],
+            gitmoji: None,
         }
     )
 }
@@ -155,7 +167,8 @@ fn body_multiline_breaking_change_no_issues() {
             subject: "commit subject".to_string(),
             body: Some("commit body\ncommit body line 2".to_string()),
             breaking_change: Some("this is a breaking change\non several line".to_string()),
-            closed_issues: None
+            footer: vec![FooterItem::BreakingChange("BREAKING CHANGE".to_string())],
+            gitmoji: None,
         }
     )
 }
@@ -184,7 +197,11 @@ fn body_multiline_breaking_change_issues() {
             subject: "commit subject".to_string(),
             body: Some("commit body\ncommit body line 2".to_string()),
             breaking_change: Some("this is a breaking change\non several line".to_string()),
-            closed_issues: Some(vec![1])
+            footer: vec![
+                FooterItem::BreakingChange("BREAKING CHANGE".to_string()),
+                FooterItem::Issues(vec![IssueRef::new(IssueAction::Closes, IssueId::Issue(1))])
+            ],
+            gitmoji: None,
         }
     )
 }
@@ -211,7 +228,11 @@ fn body_multiline_issues() {
             subject: "commit subject".to_string(),
             body: Some("commit body\ncommit body line 2".to_string()),
             breaking_change: None,
-            closed_issues: Some(vec![1])
+            footer: vec![FooterItem::Issues(vec![IssueRef::new(
+                IssueAction::Closes,
+                IssueId::Issue(1)
+            )])],
+            gitmoji: None,
         }
     )
 }
@@ -239,7 +260,263 @@ fn body_multiline_issues_2() {
             subject: "commit subject".to_string(),
             body: Some("commit body\ncommit body line 2".to_string()),
             breaking_change: None,
-            closed_issues: Some(vec![1, 2])
+            footer: vec![
+                FooterItem::Issues(vec![IssueRef::new(IssueAction::Closes, IssueId::Issue(1))]),
+                FooterItem::Issues(vec![IssueRef::new(IssueAction::Closes, IssueId::Issue(2))])
+            ],
+            gitmoji: None,
         }
     )
 }
+
+#[test]
+fn trailers() {
+    let msg = indoc!(
+        "fix: commit subject
+
+    commit body
+
+    See-also: #123
+    Reviewed-by: Z
+    Bug #4
+    Signed-off-by: John Doe <john@doe.com>"
+    );
+
+    let commit =
+        ConventionalCommitMessage::parse(msg, &Config::default().valid_commit_types()).unwrap();
+
+    assert_eq!(
+        commit,
+        ConventionalCommitMessage {
+            r#type: "fix".to_string(),
+            scope: None,
+            subject: "commit subject".to_string(),
+            body: Some("commit body".to_string()),
+            breaking_change: None,
+            footer: vec![
+                FooterItem::Trailer(Trailer::new("See-also", "#123")),
+                FooterItem::Trailer(Trailer::new("Reviewed-by", "Z")),
+                FooterItem::Trailer(Trailer {
+                    token: "Bug".to_string(),
+                    separator: TrailerSeparator::Hash,
+                    value: "4".to_string()
+                }),
+                FooterItem::Trailer(Trailer::new("Signed-off-by", "John Doe <john@doe.com>")),
+            ],
+            gitmoji: None,
+        }
+    );
+    assert_eq!(commit.to_string(), msg);
+    assert_eq!(
+        commit.trailer("signed-off-by").map(|t| t.value.as_str()),
+        Some("John Doe <john@doe.com>")
+    );
+}
+
+#[test]
+fn trailers_with_breaking_change_and_issues() {
+    let msg = indoc!(
+        "fix: commit subject
+
+    BREAKING CHANGE: this is a breaking change
+    Closes #1
+    Co-authored-by: Jane Doe <jane@doe.com>
+    Co-authored-by: John Doe <john@doe.com>"
+    );
+
+    let commit =
+        ConventionalCommitMessage::parse(msg, &Config::default().valid_commit_types()).unwrap();
+
+    assert_eq!(commit.body, None);
+    assert_eq!(
+        commit.breaking_change,
+        Some("this is a breaking change".to_string())
+    );
+    assert_eq!(
+        commit.issues(),
+        vec![&IssueRef::new(IssueAction::Closes, IssueId::Issue(1))]
+    );
+    assert_eq!(commit.trailers_by_token("co-authored-by").count(), 2);
+}
+
+#[test]
+fn trailers_order() {
+    let msg = indoc!(
+        "fix!: commit subject
+
+    Signed-off-by: John Doe <john@doe.com>
+    Closes #1, #2
+    BREAKING CHANGE: this is a breaking change
+    Refs: abc1234"
+    );
+
+    let commit =
+        ConventionalCommitMessage::parse(msg, &Config::default().valid_commit_types()).unwrap();
+
+    assert_eq!(
+        commit.footer,
+        vec![
+            FooterItem::Trailer(Trailer::new("Signed-off-by", "John Doe <john@doe.com>")),
+            FooterItem::Issues(vec![
+                IssueRef::new(IssueAction::Closes, IssueId::Issue(1)),
+                IssueRef::new(IssueAction::Closes, IssueId::Issue(2))
+            ]),
+            FooterItem::BreakingChange("BREAKING CHANGE".to_string()),
+            FooterItem::Trailer(Trailer::new("Refs", "abc1234"))
+        ]
+    );
+    assert_eq!(commit.to_string(), msg);
+
+    // NB: the footer items can be edited
+    let mut commit = commit;
+    commit.footer.remove(0);
+    commit.breaking_change = Some("".to_string());
+    assert_eq!(
+        commit.to_string(),
+        indoc!(
+            "fix!: commit subject
+
+        Closes #1, #2
+        Refs: abc1234"
+        )
+    );
+}
+
+#[test]
+fn trailers_multiline() {
+    let msg = indoc!(
+        "fix: commit subject
+
+    commit body
+
+    Reviewed-by: John Doe
+      <john@doe.com>
+    Acked-by: Jane Doe"
+    );
+
+    let commit =
+        ConventionalCommitMessage::parse(msg, &Config::default().valid_commit_types()).unwrap();
+
+    assert_eq!(commit.body, Some("commit body".to_string()));
+    let trailers = commit.trailers();
+    assert_eq!(trailers.len(), 2);
+    assert_eq!(trailers[0].value, "John Doe\n  <john@doe.com>");
+    assert_eq!(trailers[0].unfolded_value(), "John Doe <john@doe.com>");
+    assert_eq!(commit.to_string(), msg);
+}
+
+#[test]
+fn trailers_not_in_last_paragraph() {
+    let msg = indoc!(
+        "fix: commit subject
+
+    Note: this is part of the body
+    which spans several lines
+
+    Signed-off-by: John Doe"
+    );
+
+    let commit =
+        ConventionalCommitMessage::parse(msg, &Config::default().valid_commit_types()).unwrap();
+
+    assert_eq!(
+        commit.body,
+        Some("Note: this is part of the body\nwhich spans several lines".to_string())
+    );
+    assert_eq!(
+        commit.trailers(),
+        vec![&Trailer::new("Signed-off-by", "John Doe")]
+    );
+}
+
+#[test]
+fn breaking_change_hyphen_token() {
+    let msg = indoc!(
+        "fix: commit subject
+
+    BREAKING-CHANGE: this is a breaking change"
+    );
+
+    let commit =
+        ConventionalCommitMessage::parse(msg, &Config::default().valid_commit_types()).unwrap();
+
+    assert!(commit.is_breaking());
+    assert_eq!(
+        commit.breaking_change,
+        Some("this is a breaking change".to_string())
+    );
+    assert!(commit.trailers().is_empty());
+
+    // NB: the token is kept
+    let msg = indoc!(
+        "fix!: commit subject
+
+    BREAKING-CHANGE: this is a breaking change"
+    );
+    let commit =
+        ConventionalCommitMessage::parse(msg, &Config::default().valid_commit_types()).unwrap();
+    assert_eq!(commit.to_string(), msg);
+}
+
+#[test]
+fn breaking_change_marker_and_footer() {
+    let msg = indoc!(
+        "fix!: commit subject
+
+    BREAKING CHANGE: this is a breaking change"
+    );
+
+    let commit =
+        ConventionalCommitMessage::parse(msg, &Config::default().valid_commit_types()).unwrap();
+
+    assert_eq!(
+        commit.breaking_change,
+        Some("this is a breaking change".to_string())
+    );
+    assert_eq!(commit.to_string(), msg);
+}
+
+#[test]
+fn breaking_change_marker_only() {
+    let msg = indoc!("fix!: commit subject");
+
+    let commit =
+        ConventionalCommitMessage::parse(msg, &Config::default().valid_commit_types()).unwrap();
+
+    assert_eq!(commit.breaking_change_description(), Some("commit subject"));
+    assert_eq!(commit.to_string(), msg);
+}
+
+#[test]
+fn breaking_change_several_footers() {
+    let msg = indoc!(
+        "fix: commit subject
+
+    BREAKING CHANGE: this is a breaking change
+    BREAKING-CHANGE: this is another breaking change"
+    );
+
+    assert!(
+        ConventionalCommitMessage::parse(msg, &Config::default().valid_commit_types()).is_err()
+    );
+}
+
+#[test]
+fn scope_grammar() {
+    let valid_types = Config::default().valid_commit_types();
+
+    for (msg, scopes) in [
+        ("feat(api-client): commit subject", vec!["api-client"]),
+        ("fix(crates/core): commit subject", vec!["crates/core"]),
+        ("fix(v1.2): commit subject", vec!["v1.2"]),
+        ("feat(ui,api): commit subject", vec!["ui", "api"]),
+        ("feat(ui, api): commit subject", vec!["ui", "api"]),
+    ] {
+        let commit = ConventionalCommitMessage::parse(msg, &valid_types).unwrap();
+        assert_eq!(commit.scopes(), scopes);
+        assert_eq!(commit.to_string(), msg);
+    }
+
+    assert!(ConventionalCommitMessage::parse("feat(ui,): commit subject", &valid_types).is_err());
+    assert!(ConventionalCommitMessage::parse("feat(ui#1): commit subject", &valid_types).is_err());
+}
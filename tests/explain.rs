@@ -0,0 +1,110 @@
+//! Testing of the next version explanation
+
+use gitext::{
+    config::{Bump, Config},
+    conventional::ConventionalCommitMessage,
+    version::{get_commit_bump_rule, CommitBump, CommitStatus, VersionExplanation},
+};
+use indoc::indoc;
+
+fn commit(id: &str, summary: &str, status: CommitStatus, bump: Bump, rule: &str) -> CommitBump {
+    CommitBump {
+        id: id.to_string(),
+        summary: summary.to_string(),
+        r#type: summary.split_once(':').map(|(t, _)| t.to_string()),
+        status,
+        bump,
+        rule: (!rule.is_empty()).then(|| rule.to_string()),
+    }
+}
+
+#[test]
+fn bump_rules() {
+    let config = Config::default();
+    let rule = |msg: &str| {
+        let c = ConventionalCommitMessage::parse_with_config(msg, &config).unwrap();
+        get_commit_bump_rule(&config, &c)
+    };
+
+    assert_eq!(
+        rule("feat: add endpoint"),
+        (Bump::Minor, "commit.types_inc_minor".to_string())
+    );
+    assert_eq!(
+        rule("ci: add workflow"),
+        (Bump::None, "default".to_string())
+    );
+    assert_eq!(
+        rule("docs!: drop guide"),
+        (Bump::Major, "breaking change".to_string())
+    );
+
+    let mut config = Config::default();
+    config
+        .commit
+        .bump
+        .insert("docs(api)".to_string(), Bump::Minor);
+    assert_eq!(
+        config.type_bump_rule("docs", &["ui", "api"]),
+        (Bump::Minor, "commit.bump.\"docs(api)\"".to_string())
+    );
+    assert_eq!(
+        config.type_bump_rule("chore", &[]),
+        (Bump::None, "default".to_string())
+    );
+    assert_eq!(
+        config.type_bump_rule("perf", &[]),
+        (Bump::Patch, "default".to_string())
+    );
+}
+
+#[test]
+fn explanation() {
+    let explanation = VersionExplanation {
+        current: Some("1.2.0".to_string()),
+        next: Some("1.3.0".to_string()),
+        bump: Bump::Minor,
+        commits: vec![
+            commit(
+                "a1b2c3d4e5",
+                "ci: add workflow",
+                CommitStatus::Counted,
+                Bump::None,
+                "commit.bump.ci",
+            ),
+            commit(
+                "b2c3d4e5f6",
+                "wip",
+                CommitStatus::Unconventional,
+                Bump::None,
+                "",
+            ),
+            commit(
+                "c3d4e5f6a7",
+                "feat: add endpoint",
+                CommitStatus::Counted,
+                Bump::Minor,
+                "commit.bump.feat",
+            ),
+        ],
+        decisive_commit: Some("c3d4e5f6a7".to_string()),
+    };
+
+    assert_eq!(
+        explanation.to_table(),
+        indoc!(
+            "COMMIT   TYPE  STATUS          BUMP   RULE              SUMMARY
+            a1b2c3d  ci    counted         none   commit.bump.ci    ci: add workflow
+            b2c3d4e  -     unconventional  none   -                 wip
+            c3d4e5f  feat  counted         minor  commit.bump.feat  feat: add endpoint
+
+            Decisive commit: c3d4e5f feat: add endpoint (minor, commit.bump.feat)"
+        )
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&explanation.to_json().unwrap()).unwrap();
+    assert_eq!(json["next"], "1.3.0");
+    assert_eq!(json["commits"][1]["status"], "unconventional");
+    assert_eq!(json["commits"][2]["type"], "feat");
+    assert_eq!(json["decisive_commit"], "c3d4e5f6a7");
+}
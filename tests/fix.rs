@@ -0,0 +1,115 @@
+//! Testing of the lint fixes
+
+use gitext::{
+    config::Config,
+    conventional::ConventionalCommitMessage,
+    lint::{apply_fixes, render_diff, strip_comments, Case, LintLevel, Span},
+};
+use indoc::indoc;
+
+#[test]
+fn span_byte_range() {
+    let msg = "feat: ✨ add\nbody";
+    assert_eq!(Span::new(1, 7, 1).byte_range(msg), Some((6, 9)));
+    assert_eq!(Span::new(2, 1, 0).byte_range(msg), Some((14, 14)));
+    assert_eq!(Span::new(2, 5, 0).byte_range(msg), Some((18, 18)));
+    assert_eq!(Span::new(3, 1, 0).byte_range(msg), None);
+    assert_eq!(Span::new(2, 6, 0).byte_range(msg), None);
+}
+
+#[test]
+fn fix_mechanical_violations() {
+    let mut config = Config::default();
+    config.lint.subject_full_stop = LintLevel::Error;
+    config.lint.scope_case.value = Case::LowerCase;
+
+    let msg = indoc!(
+        "Fix(UI): Add the button.
+        commit body
+        "
+    );
+    let fixed = ConventionalCommitMessage::fix(msg, &config);
+    assert_eq!(
+        fixed,
+        indoc!(
+            "fix(ui): add the button
+
+            commit body
+            "
+        )
+    );
+
+    let (_, diagnostics) = ConventionalCommitMessage::lint(&fixed, &config);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn fix_keeps_unfixable_violations() {
+    let config = Config::default();
+
+    let msg = "Foo: Add the button";
+    let fixed = ConventionalCommitMessage::fix(msg, &config);
+    assert_eq!(fixed, "Foo: add the button");
+
+    let (_, diagnostics) = ConventionalCommitMessage::lint(&fixed, &config);
+    let rules: Vec<_> = diagnostics.iter().map(|d| d.rule.as_str()).collect();
+    assert_eq!(rules, vec!["type-enum"]);
+
+    // NB: special commits are not linted, so not fixed
+    let msg = "fixup! Fix: Add the button";
+    assert_eq!(ConventionalCommitMessage::fix(msg, &config), msg);
+}
+
+#[test]
+fn overlapping_fixes() {
+    let mut config = Config::default();
+    config.lint.subject_full_stop = LintLevel::Error;
+    config.lint.subject_case.value = Case::LowerCase;
+
+    let msg = "fix: Add The Button.";
+    let (_, diagnostics) = ConventionalCommitMessage::lint(msg, &config);
+    assert_eq!(diagnostics.len(), 2);
+
+    // the 2nd fix is applied in another pass
+    assert_eq!(apply_fixes(msg, &diagnostics), "fix: Add The Button");
+    assert_eq!(
+        ConventionalCommitMessage::fix(msg, &config),
+        "fix: add the button"
+    );
+}
+
+#[test]
+fn diff() {
+    let old = "Fix: Add\nbody";
+    let new = "fix: add\n\nbody";
+    assert_eq!(render_diff(old, new), "-Fix: Add\n+fix: add\n+\n body");
+    assert_eq!(render_diff(old, old), " Fix: Add\n body");
+}
+
+#[test]
+fn strip_comment_lines() {
+    let msg = indoc!(
+        "Fix: add endpoint
+
+        # Please enter the commit message for your changes.
+        #
+        body
+        # ------------------------ >8 ------------------------
+        diff --git a/file b/file
+        "
+    );
+    let stripped = strip_comments(msg, "#");
+    assert_eq!(stripped, "Fix: add endpoint\n\nbody\n");
+    assert_eq!(
+        ConventionalCommitMessage::fix(&stripped, &Config::default()),
+        "fix: add endpoint\n\nbody\n"
+    );
+
+    // custom comment character (`core.commentChar`)
+    let msg = "feat: add endpoint\n\n#12 is fixed\n; comment\n";
+    assert_eq!(
+        strip_comments(msg, ";"),
+        "feat: add endpoint\n\n#12 is fixed\n"
+    );
+    assert_eq!(strip_comments("# only comments\n", "#"), "");
+}
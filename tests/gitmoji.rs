@@ -0,0 +1,118 @@
+//! Testing of gitmoji commits
+
+use gitext::{
+    config::Config,
+    conventional::ConventionalCommitMessage,
+    gitmoji::{emoji_to_shortcode, shortcode_to_emoji, Gitmoji, GitmojiFormat, GitmojiPosition},
+    lint::Span,
+};
+
+/// Returns a config with the gitmoji mode enabled
+fn gitmoji_config() -> Config {
+    let mut config = Config::default();
+    config.gitmoji.enabled = true;
+    config
+}
+
+#[test]
+fn lookup() {
+    assert_eq!(shortcode_to_emoji("sparkles"), Some("✨"));
+    assert_eq!(shortcode_to_emoji("unknown"), None);
+    assert_eq!(emoji_to_shortcode("🐛"), Some("bug"));
+    // NB: the variation selector is optional
+    assert_eq!(emoji_to_shortcode("⚡"), Some("zap"));
+    assert_eq!(emoji_to_shortcode("⚡️"), Some("zap"));
+}
+
+#[test]
+fn parse_before_type() {
+    let config = gitmoji_config();
+
+    let commit =
+        ConventionalCommitMessage::parse_with_config("✨ feat(api): add endpoint", &config)
+            .unwrap();
+    assert_eq!(commit.r#type, "feat");
+    assert_eq!(commit.scope, Some("api".to_string()));
+    assert_eq!(commit.subject, "add endpoint");
+    assert_eq!(
+        commit.gitmoji,
+        Some(Gitmoji::new(
+            "sparkles",
+            GitmojiPosition::BeforeType,
+            GitmojiFormat::Emoji
+        ))
+    );
+    assert_eq!(commit.to_string(), "✨ feat(api): add endpoint");
+
+    let commit =
+        ConventionalCommitMessage::parse_with_config(":bug: fix: handle errors", &config).unwrap();
+    assert_eq!(commit.r#type, "fix");
+    assert_eq!(commit.subject, "handle errors");
+    assert_eq!(
+        commit.gitmoji,
+        Some(Gitmoji::new(
+            "bug",
+            GitmojiPosition::BeforeType,
+            GitmojiFormat::Shortcode
+        ))
+    );
+    assert_eq!(commit.to_string(), ":bug: fix: handle errors");
+}
+
+#[test]
+fn parse_before_subject() {
+    let config = gitmoji_config();
+
+    let commit =
+        ConventionalCommitMessage::parse_with_config("perf: ⚡ faster parsing", &config).unwrap();
+    assert_eq!(commit.r#type, "perf");
+    assert_eq!(commit.subject, "faster parsing");
+    assert_eq!(
+        commit.gitmoji,
+        Some(Gitmoji::new(
+            "zap",
+            GitmojiPosition::BeforeSubject,
+            GitmojiFormat::Emoji
+        ))
+    );
+    assert_eq!(commit.to_string(), "perf: ⚡️ faster parsing");
+
+    let commit =
+        ConventionalCommitMessage::parse_with_config("docs: :memo: update readme", &config)
+            .unwrap();
+    assert_eq!(commit.subject, "update readme");
+    assert_eq!(commit.to_string(), "docs: :memo: update readme");
+}
+
+#[test]
+fn gitmoji_disabled() {
+    // NB: without the gitmoji mode, a leading gitmoji is an invalid type
+    let config = Config::default();
+    assert!(
+        ConventionalCommitMessage::parse_with_config("✨ feat: add endpoint", &config).is_err()
+    );
+
+    let (commit, _) = ConventionalCommitMessage::lint("feat: ✨ add endpoint", &config);
+    let commit = commit.unwrap();
+    assert_eq!(commit.subject, "✨ add endpoint");
+    assert_eq!(commit.gitmoji, None);
+}
+
+#[test]
+fn lint_spans() {
+    let config = gitmoji_config();
+
+    let (commit, diagnostics) = ConventionalCommitMessage::lint("✨ Feat: Add endpoint", &config);
+    assert!(commit.is_some());
+    let spans: Vec<_> = diagnostics
+        .iter()
+        .map(|d| (d.rule.as_str(), d.span))
+        .collect();
+    assert_eq!(
+        spans,
+        vec![
+            ("type-case", Span::new(1, 3, 4)),
+            ("subject-case", Span::new(1, 9, 1))
+        ]
+    );
+}
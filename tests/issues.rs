@@ -0,0 +1,146 @@
+//! Testing of issue references
+
+use gitext::{
+    config::{Config, IssuesConfig},
+    conventional::{
+        issue::{IssueAction, IssueId, IssueRef},
+        ConventionalCommitMessage, Trailer,
+    },
+    lint::{LintLevel, Severity},
+};
+use indoc::indoc;
+
+#[test]
+fn parse_refs() {
+    let msg = indoc!(
+        "fix: commit subject
+
+    Closes #1
+    Fixes: !45, org/repo#12
+    Resolves: PROJ-123
+    Refs #7"
+    );
+
+    let commit = ConventionalCommitMessage::parse_with_config(msg, &Config::default()).unwrap();
+
+    assert_eq!(
+        commit.issues().into_iter().cloned().collect::<Vec<_>>(),
+        vec![
+            IssueRef::new(IssueAction::Closes, IssueId::Issue(1)),
+            IssueRef::new(IssueAction::Fixes, IssueId::MergeRequest(45)),
+            IssueRef {
+                action: IssueAction::Fixes,
+                repo: Some("org/repo".to_string()),
+                id: IssueId::Issue(12),
+            },
+            IssueRef::new(IssueAction::Resolves, IssueId::Key("PROJ-123".to_string())),
+            IssueRef::new(IssueAction::Refs, IssueId::Issue(7)),
+        ]
+    );
+    assert!(commit.trailers().is_empty());
+    assert_eq!(commit.closed_issues().count(), 4);
+
+    // NB: the references of a trailer are kept on the same line
+    assert_eq!(commit.to_string(), msg);
+}
+
+#[test]
+fn key_patterns() {
+    let msg = indoc!(
+        "fix: commit subject
+
+    Closes: ab-12"
+    );
+
+    // default pattern
+    let (_, diagnostics) = ConventionalCommitMessage::lint(msg, &Config::default());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, "footer-issue");
+
+    // custom pattern
+    let mut config = Config::default();
+    config.issues.key_patterns = vec!["[a-z]+-[0-9]+".to_string()];
+    let commit = ConventionalCommitMessage::parse_with_config(msg, &config).unwrap();
+    assert_eq!(
+        commit.issues(),
+        vec![&IssueRef::new(
+            IssueAction::Closes,
+            IssueId::Key("ab-12".to_string())
+        )]
+    );
+}
+
+#[test]
+fn refs_other_values() {
+    // NB: `Refs` trailers which are not issues are kept as trailers
+    let msg = indoc!(
+        "revert: fix: commit subject
+
+    Refs: 8f1c2a7"
+    );
+
+    let commit = ConventionalCommitMessage::parse_with_config(msg, &Config::default()).unwrap();
+    assert!(commit.issues().is_empty());
+    assert_eq!(commit.trailers(), vec![&Trailer::new("Refs", "8f1c2a7")]);
+}
+
+#[test]
+fn closes_other_values() {
+    // NB: closing trailers which are not issues are kept as trailers, with a warning
+    let msg = indoc!(
+        "fix: commit subject
+
+    Fixes: abc1234 (\"feat: add endpoint\")
+    Closes: crash on load"
+    );
+
+    let (commit, diagnostics) = ConventionalCommitMessage::lint(msg, &Config::default());
+    let commit = commit.unwrap();
+    assert!(commit.issues().is_empty());
+    assert_eq!(
+        commit.trailers(),
+        vec![
+            &Trailer::new("Fixes", "abc1234 (\"feat: add endpoint\")"),
+            &Trailer::new("Closes", "crash on load")
+        ]
+    );
+    assert_eq!(commit.to_string(), msg);
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics
+        .iter()
+        .all(|d| d.rule == "footer-issue" && d.severity == Severity::Warning));
+
+    let mut config = Config::default();
+    config.lint.footer_issue = LintLevel::Error;
+    assert!(ConventionalCommitMessage::parse_with_config(msg, &config).is_err());
+}
+
+#[test]
+fn urls() {
+    let origin = "https://github.com/me/project";
+    let mut config = IssuesConfig::default();
+
+    let issue = IssueRef::new(IssueAction::Closes, IssueId::Issue(12));
+    assert_eq!(
+        issue.url(origin, &config),
+        Some("https://github.com/me/project/issues/12".to_string())
+    );
+
+    let issue = IssueRef {
+        action: IssueAction::Closes,
+        repo: Some("org/repo".to_string()),
+        id: IssueId::Issue(12),
+    };
+    assert_eq!(
+        issue.url(origin, &config),
+        Some("https://github.com/org/repo/issues/12".to_string())
+    );
+
+    let issue = IssueRef::new(IssueAction::Refs, IssueId::Key("PROJ-123".to_string()));
+    assert_eq!(issue.url(origin, &config), None);
+    config.key_url = Some("https://jira.example.com/browse/{{key}}".to_string());
+    assert_eq!(
+        issue.url(origin, &config),
+        Some("https://jira.example.com/browse/PROJ-123".to_string())
+    );
+}
@@ -0,0 +1,79 @@
+//! Testing of the git log parsing, on a test repo
+
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use gitext::git::{LogQuery, SignatureStatus};
+
+use common::{git, temp_repo};
+
+#[test]
+fn log_parse() {
+    let repo = temp_repo();
+    let dir = repo.path();
+
+    fs::create_dir_all(dir.join("docs")).unwrap();
+    fs::write(dir.join("docs/a file.md"), "a").unwrap();
+    fs::write(dir.join("é.txt"), "b").unwrap();
+    git(dir, &["add", "-A"]);
+    git(
+        dir,
+        &[
+            "commit",
+            "-q",
+            "-m",
+            "feat: first\n\n----------\nhash: 0000\nmessage: not a field",
+        ],
+    );
+    git(dir, &["checkout", "-q", "-b", "feature"]);
+    fs::write(dir.join("b.txt"), "b").unwrap();
+    git(dir, &["add", "-A"]);
+    git(dir, &["commit", "-q", "-m", "fix: second"]);
+    git(dir, &["checkout", "-q", "main"]);
+    git(
+        dir,
+        &["commit", "-q", "--allow-empty", "-m", "chore: empty"],
+    );
+    git(
+        dir,
+        &["merge", "-q", "--no-ff", "feature", "-m", "Merge feature"],
+    );
+
+    let commits = LogQuery::new().dir(dir).run().unwrap();
+    assert_eq!(commits.len(), 4);
+    // NB: the commits have the same date, so the branches commits are not ordered
+    let commit = |message: &str| commits.iter().find(|c| c.message == message).unwrap();
+    let first = commit("feat: first\n\n----------\nhash: 0000\nmessage: not a field");
+    let second = commit("fix: second");
+    let empty = commit("chore: empty");
+
+    let merge = commit("Merge feature");
+    assert!(merge.merge);
+    assert_eq!(merge.parents, vec![empty.id.clone(), second.id.clone()]);
+    assert!(merge.files.is_empty());
+
+    assert!(!first.merge);
+    assert!(first.parents.is_empty());
+    assert_eq!(
+        first.files,
+        vec![PathBuf::from("docs/a file.md"), PathBuf::from("é.txt")]
+    );
+    assert_eq!(
+        (first.author.as_str(), first.author_email.as_str()),
+        ("Alice", "alice@example.com")
+    );
+    assert_eq!(
+        (first.committer.as_str(), first.committer_email.as_str()),
+        ("Bob", "bob@example.com")
+    );
+    assert_eq!(first.timestamp.to_rfc3339(), "2022-01-02T01:04:05+00:00");
+    assert_eq!(
+        first.committer_timestamp.to_rfc3339(),
+        "2022-01-03T00:00:00+00:00"
+    );
+    assert_eq!(first.signature, SignatureStatus::None);
+
+    assert!(empty.files.is_empty());
+    assert_eq!(second.files, vec![PathBuf::from("b.txt")]);
+}
@@ -0,0 +1,57 @@
+//! Testing of the git log queries (on the repo history)
+
+use std::path::Path;
+
+use chrono::{TimeZone, Utc};
+use gitext::git::{git_log, LogQuery};
+
+#[test]
+fn log_paths() {
+    let commits = LogQuery::new().path("src/version").run().unwrap();
+    assert!(!commits.is_empty());
+    for c in &commits {
+        assert!(
+            c.files.iter().any(|f| f.starts_with("src/version")),
+            "{}: {:?}",
+            c.id,
+            c.files
+        );
+    }
+
+    let all = git_log("").unwrap();
+    assert!(commits.len() < all.len());
+    assert!(all
+        .iter()
+        .any(|c| c.files.contains(&Path::new("Cargo.toml").to_path_buf())));
+}
+
+#[test]
+fn log_filters() {
+    let commits = LogQuery::new()
+        .range("HEAD~1..HEAD")
+        .first_parent()
+        .no_merges()
+        .run()
+        .unwrap();
+    assert!(commits.len() <= 1);
+
+    let commits = LogQuery::new()
+        .author("nobody@gitext.invalid")
+        .run()
+        .unwrap();
+    assert!(commits.is_empty());
+
+    let commits = LogQuery::new()
+        .until(Utc.ymd(1990, 1, 1).and_hms(0, 0, 0))
+        .run()
+        .unwrap();
+    assert!(commits.is_empty());
+
+    let commits = LogQuery::new()
+        .since(Utc.ymd(1990, 1, 1).and_hms(0, 0, 0))
+        .run()
+        .unwrap();
+    assert_eq!(commits.len(), git_log("").unwrap().len());
+
+    assert!(LogQuery::new().range("no-such-ref..").run().is_err());
+}
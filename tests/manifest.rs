@@ -0,0 +1,413 @@
+//! Testing of the manifest version bumpers
+
+mod common;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use gitext::{
+    config::{BumpFile, Config, ManifestKind, ReleaseConfig},
+    version::manifest::bump_file,
+};
+use indoc::indoc;
+
+use common::temp_dir;
+
+fn bump(dir: &Path, path: &str, pattern: Option<&str>) -> Vec<PathBuf> {
+    let file = BumpFile {
+        path: PathBuf::from(path),
+        kind: None,
+        pattern: pattern.map(|p| p.to_string()),
+    };
+    bump_file(dir, &file, "1.3.0").unwrap()
+}
+
+fn read(dir: &Path, path: &str) -> String {
+    fs::read_to_string(dir.join(path)).unwrap()
+}
+
+#[test]
+fn manifest_kinds() {
+    let kind = |path: &str| ManifestKind::from_path(Path::new(path));
+    assert_eq!(kind("crates/core/Cargo.toml"), Some(ManifestKind::Cargo));
+    assert_eq!(kind("package-lock.json"), Some(ManifestKind::Npm));
+    assert_eq!(kind("charts/app/Chart.yaml"), Some(ManifestKind::Helm));
+    assert_eq!(kind("VERSION"), None);
+}
+
+#[test]
+fn config() {
+    let s = indoc!(
+        r#"
+        bump_commands = []
+
+        [[bump_files]]
+        path = "Cargo.toml"
+
+        [[bump_files]]
+        path = "VERSION"
+        pattern = "(.*)"
+
+        [channels]
+        next = "beta"
+        "#
+    );
+    let config = Config {
+        release: toml::from_str::<ReleaseConfig>(s).unwrap(),
+        ..Default::default()
+    };
+    let kinds: Vec<_> = config.release.bump_files.iter().map(|f| f.kind()).collect();
+    assert_eq!(
+        kinds,
+        vec![Some(ManifestKind::Cargo), Some(ManifestKind::Regex)]
+    );
+
+    // NB: the files are serialized before the channels
+    let config_str = toml::to_string(&config).unwrap();
+    assert!(toml::from_str::<Config>(&config_str).is_ok());
+}
+
+#[test]
+fn cargo_workspace() {
+    let tmp = temp_dir(&[
+        (
+            "Cargo.toml",
+            indoc!(
+                r#"
+                [package]
+                name = "app" # the app
+                version = "1.2.0"
+
+                [dependencies]
+                core = { path = "crates/core", version = "=1.2.0" }
+                serde = { version = "1.0" }
+
+                [workspace]
+                members = ["crates/*"]
+                "#
+            ),
+        ),
+        (
+            "crates/core/Cargo.toml",
+            indoc!(
+                r#"
+                [package]
+                name = "core"
+                version   =   '1.2.0'
+                "#
+            ),
+        ),
+        (
+            "Cargo.lock",
+            indoc!(
+                r#"
+                [[package]]
+                name = "app"
+                version = "1.2.0"
+
+                [[package]]
+                name = "core"
+                version = "1.2.0"
+
+                [[package]]
+                name = "serde"
+                version = "1.2.0"
+                source = "registry+https://github.com/rust-lang/crates.io-index"
+                "#
+            ),
+        ),
+    ]);
+    let dir = tmp.path();
+
+    let changed = bump(dir, "Cargo.toml", None);
+    assert_eq!(
+        changed,
+        vec![
+            PathBuf::from("Cargo.toml"),
+            PathBuf::from("crates/core/Cargo.toml"),
+            PathBuf::from("Cargo.lock")
+        ]
+    );
+    assert_eq!(
+        read(dir, "Cargo.toml"),
+        indoc!(
+            r#"
+            [package]
+            name = "app" # the app
+            version = "1.3.0"
+
+            [dependencies]
+            core = { path = "crates/core", version = "=1.3.0" }
+            serde = { version = "1.0" }
+
+            [workspace]
+            members = ["crates/*"]
+            "#
+        )
+    );
+    assert!(read(dir, "crates/core/Cargo.toml").contains("version   =   '1.3.0'"));
+    let lock = read(dir, "Cargo.lock");
+    assert_eq!(lock.matches("version = \"1.3.0\"").count(), 2);
+    assert!(lock.contains("name = \"serde\"\nversion = \"1.2.0\""));
+
+    // NB: unchanged files are not reported
+    assert!(bump(dir, "Cargo.toml", None).is_empty());
+}
+
+#[test]
+fn cargo_workspace_member() {
+    let tmp = temp_dir(&[
+        (
+            "Cargo.toml",
+            indoc!(
+                r#"
+                [workspace]
+                members = ["crates/*"]
+                "#
+            ),
+        ),
+        (
+            "crates/core/Cargo.toml",
+            indoc!(
+                r#"
+                [package]
+                name = "core"
+                version = "1.2.0"
+                "#
+            ),
+        ),
+        (
+            "crates/cli/Cargo.toml",
+            indoc!(
+                r#"
+                [package]
+                name = "cli"
+                version = "0.4.0"
+
+                [dependencies]
+                core = { path = "../core", version = "1.2" }
+                "#
+            ),
+        ),
+        (
+            "Cargo.lock",
+            indoc!(
+                r#"
+                [[package]]
+                name = "cli"
+                version = "0.4.0"
+
+                [[package]]
+                name = "core"
+                version = "1.2.0"
+                "#
+            ),
+        ),
+    ]);
+    let dir = tmp.path();
+
+    // NB: the sibling members and the lock file of the workspace root are updated
+    let changed = bump(dir, "crates/core/Cargo.toml", None);
+    assert_eq!(
+        changed,
+        vec![
+            PathBuf::from("crates/core/Cargo.toml"),
+            PathBuf::from("crates/cli/Cargo.toml"),
+            PathBuf::from("Cargo.lock")
+        ]
+    );
+    assert!(read(dir, "crates/core/Cargo.toml").contains("version = \"1.3.0\""));
+    let cli = read(dir, "crates/cli/Cargo.toml");
+    assert!(cli.contains("version = \"0.4.0\""));
+    assert!(cli.contains("core = { path = \"../core\", version = \"1.3.0\" }"));
+    let lock = read(dir, "Cargo.lock");
+    assert!(lock.contains("name = \"cli\"\nversion = \"0.4.0\""));
+    assert!(lock.contains("name = \"core\"\nversion = \"1.3.0\""));
+
+    // NB: a package outside of the workspace members keeps its own lock file
+    let tmp = temp_dir(&[
+        (
+            "Cargo.toml",
+            indoc!(
+                r#"
+                [workspace]
+                members = ["crates/*"]
+                exclude = ["crates/tool"]
+                "#
+            ),
+        ),
+        (
+            "crates/tool/Cargo.toml",
+            indoc!(
+                r#"
+                [package]
+                name = "tool"
+                version = "1.2.0"
+                "#
+            ),
+        ),
+        (
+            "crates/tool/Cargo.lock",
+            indoc!(
+                r#"
+                [[package]]
+                name = "tool"
+                version = "1.2.0"
+                "#
+            ),
+        ),
+        ("Cargo.lock", ""),
+    ]);
+    let dir = tmp.path();
+    assert_eq!(
+        bump(dir, "crates/tool/Cargo.toml", None),
+        vec![
+            PathBuf::from("crates/tool/Cargo.toml"),
+            PathBuf::from("crates/tool/Cargo.lock")
+        ]
+    );
+}
+
+#[test]
+fn npm() {
+    let tmp = temp_dir(&[
+        (
+            "package.json",
+            indoc!(
+                r#"
+                {
+                  "name": "app",
+                  "dependencies": { "lib": { "version": "0.1.0" } },
+                  "version": "1.2.0",
+                  "scripts": { "build": "echo \"version\"" }
+                }
+                "#
+            ),
+        ),
+        (
+            "package-lock.json",
+            indoc!(
+                r#"
+                {
+                  "name": "app",
+                  "version": "1.2.0",
+                  "packages": {
+                    "": { "name": "app", "version": "1.2.0" },
+                    "node_modules/lib": { "version": "0.1.0" }
+                  }
+                }
+                "#
+            ),
+        ),
+    ]);
+    let dir = tmp.path();
+
+    let changed = bump(dir, "package.json", None);
+    assert_eq!(changed.len(), 2);
+    let package = read(dir, "package.json");
+    assert!(package.contains(r#""version": "1.3.0","#));
+    assert!(package.contains(r#"{ "version": "0.1.0" }"#));
+    let lock = read(dir, "package-lock.json");
+    assert_eq!(lock.matches("1.3.0").count(), 2);
+    assert!(lock.contains(r#""node_modules/lib": { "version": "0.1.0" }"#));
+}
+
+#[test]
+fn pyproject_helm_maven() {
+    let tmp = temp_dir(&[
+        (
+            "pyproject.toml",
+            indoc!(
+                r#"
+                [build-system]
+                requires = ["poetry-core"]
+
+                [tool.poetry]
+                name = "app"
+                version = "1.2.0"
+                "#
+            ),
+        ),
+        (
+            "Chart.yaml",
+            indoc!(
+                r#"
+                apiVersion: v2
+                name: app
+                version: "1.2.0" # chart version
+                dependencies:
+                  - name: db
+                    version: 1.2.0
+                "#
+            ),
+        ),
+        (
+            "pom.xml",
+            indoc!(
+                r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <project>
+                  <parent>
+                    <version>1.0.0</version>
+                  </parent>
+                  <!-- <version>0.0.0</version> -->
+                  <artifactId>app</artifactId>
+                  <version>1.2.0</version>
+                  <dependencies>
+                    <dependency><version>1.2.0</version></dependency>
+                  </dependencies>
+                </project>
+                "#
+            ),
+        ),
+    ]);
+    let dir = tmp.path();
+
+    bump(dir, "pyproject.toml", None);
+    assert!(read(dir, "pyproject.toml").contains("name = \"app\"\nversion = \"1.3.0\"\n"));
+
+    bump(dir, "Chart.yaml", None);
+    let chart = read(dir, "Chart.yaml");
+    assert!(chart.contains("version: \"1.3.0\" # chart version\n"));
+    assert!(chart.contains("    version: 1.2.0\n"));
+
+    bump(dir, "pom.xml", None);
+    let pom = read(dir, "pom.xml");
+    assert!(pom.contains("<artifactId>app</artifactId>\n  <version>1.3.0</version>"));
+    assert_eq!(pom.matches("1.3.0").count(), 1);
+}
+
+#[test]
+fn regex() {
+    let tmp = temp_dir(&[(
+        "src/version.py",
+        "__version__ = \"1.2.0\"\nVERSION = \"1.2.0\"\n",
+    )]);
+    let dir = tmp.path();
+
+    bump(
+        dir,
+        "src/version.py",
+        Some(r#"__version__ = "(?P<version>[^"]*)""#),
+    );
+    assert_eq!(
+        read(dir, "src/version.py"),
+        "__version__ = \"1.3.0\"\nVERSION = \"1.2.0\"\n"
+    );
+
+    let file = BumpFile {
+        path: PathBuf::from("src/version.py"),
+        kind: None,
+        pattern: Some("RELEASE = (.*)".to_string()),
+    };
+    assert!(bump_file(dir, &file, "1.3.0").is_err());
+
+    let file = BumpFile {
+        path: PathBuf::from("VERSION"),
+        kind: None,
+        pattern: None,
+    };
+    assert!(bump_file(dir, &file, "1.3.0").is_err());
+}
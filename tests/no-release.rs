@@ -0,0 +1,64 @@
+//! Testing of the releases without version bump, on a test repo
+
+mod common;
+
+use std::process::Command;
+
+use gitext::{commands::shared::EXIT_NO_RELEASE, config::Config};
+use serde_json::Value;
+
+use common::{git, temp_repo};
+
+#[test]
+fn no_release() {
+    let repo = temp_repo();
+    let dir = repo.path();
+
+    Config::default().save(dir).unwrap();
+    git(dir, &["add", "-A"]);
+    git(dir, &["commit", "-q", "-m", "feat: first"]);
+    git(dir, &["tag", "-a", "v1.0.0", "-m", "v1.0.0"]);
+    git(
+        dir,
+        &["commit", "-q", "--allow-empty", "-m", "ci: add workflow"],
+    );
+    git(
+        dir,
+        &["commit", "-q", "--allow-empty", "-m", "docs: update readme"],
+    );
+    git(
+        dir,
+        &["commit", "-q", "--allow-empty", "-m", "chore: update deps"],
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_git-bump"))
+        .arg("--explain=json")
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(EXIT_NO_RELEASE), "{output:?}");
+    let explanation: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(explanation["current"], "1.0.0");
+    assert_eq!(explanation["next"], Value::Null);
+    assert_eq!(explanation["bump"], "none");
+    let commits = explanation["commits"].as_array().unwrap();
+    assert_eq!(commits.len(), 3);
+    assert!(commits
+        .iter()
+        .all(|c| c["status"] == "counted" && c["bump"] == "none"));
+
+    for bin in [
+        env!("CARGO_BIN_EXE_git-bump"),
+        env!("CARGO_BIN_EXE_git-release"),
+    ] {
+        let output = Command::new(bin).current_dir(dir).output().unwrap();
+        assert_eq!(output.status.code(), Some(EXIT_NO_RELEASE), "{output:?}");
+    }
+    // NB: nothing is released
+    let output = Command::new("git")
+        .arg("tag")
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "v1.0.0\n");
+}
@@ -0,0 +1,110 @@
+//! Testing of the monorepo packages
+
+use std::path::PathBuf;
+
+use gitext::{
+    config::{BumpCommand, Config},
+    version::tag::tag_pattern,
+};
+use indoc::indoc;
+
+fn test_config() -> Config {
+    let packages = toml::from_str(indoc!(
+        r#"
+        [core]
+        path = "crates/core"
+        bump_commands = ["cargo check", { cmd = "make", cwd = "build" }]
+
+        [[core.bump_files]]
+        path = "Cargo.toml"
+
+        [web]
+        path = "web"
+        tag_pattern = "web-v{version}"
+        changelog = "docs/WEB_CHANGELOG.md"
+        "#
+    ))
+    .unwrap();
+    Config {
+        packages,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn package_config() {
+    let config = test_config();
+
+    let core = config.for_package("core").unwrap();
+    assert_eq!(core.current_package().unwrap().0, "core");
+    assert_eq!(
+        tag_pattern(&core).unwrap().format("1.2.0"),
+        "core@1.2.0".to_string()
+    );
+    assert_eq!(
+        core.changelog.changelog_file(),
+        PathBuf::from("crates/core/CHANGELOG.md")
+    );
+    assert_eq!(
+        core.changelog.release_notes_file(),
+        PathBuf::from("crates/core/CHANGELOG_RELEASE.md")
+    );
+    assert_eq!(
+        core.release.bump_files[0].path,
+        PathBuf::from("crates/core/Cargo.toml")
+    );
+    let cwds: Vec<_> = core
+        .release
+        .bump_commands
+        .iter()
+        .map(BumpCommand::config)
+        .map(|c| c.cwd.unwrap())
+        .collect();
+    assert_eq!(
+        cwds,
+        vec![
+            PathBuf::from("crates/core"),
+            PathBuf::from("crates/core/build")
+        ]
+    );
+
+    let web = config.for_package("web").unwrap();
+    assert_eq!(
+        tag_pattern(&web).unwrap().parse("web-v0.3.0"),
+        Some("0.3.0")
+    );
+    assert_eq!(tag_pattern(&web).unwrap().parse("core@0.3.0"), None);
+    assert_eq!(
+        web.changelog.changelog_file(),
+        PathBuf::from("docs/WEB_CHANGELOG.md")
+    );
+
+    assert!(config.for_package("api").is_err());
+}
+
+#[test]
+fn release_configs() {
+    let config = test_config();
+
+    let configs = config.release_configs(&[], false).unwrap();
+    assert_eq!(configs.len(), 1);
+    assert!(configs[0].current_package().is_none());
+
+    let configs = config.release_configs(&[], true).unwrap();
+    let names: Vec<_> = configs
+        .iter()
+        .map(|c| c.current_package().unwrap().0)
+        .collect();
+    assert_eq!(names, vec!["core", "web"]);
+
+    let configs = config.release_configs(&["web".to_string()], false).unwrap();
+    assert_eq!(configs[0].current_package().unwrap().0, "web");
+
+    assert!(config.release_configs(&["api".to_string()], false).is_err());
+    assert!(Config::default().release_configs(&[], true).is_err());
+
+    // NB: the packages are serialized after the other sections
+    let config_str = toml::to_string(&config).unwrap();
+    let config = toml::from_str::<Config>(&config_str).unwrap();
+    assert_eq!(config.packages.len(), 2);
+}
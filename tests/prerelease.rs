@@ -0,0 +1,73 @@
+//! Testing of the pre-release and pre-1.0 versions
+
+use gitext::{
+    config::{Bump, PreStablePolicy},
+    version::{bump_version, get_next_prerelease},
+};
+use semver::Version;
+
+#[test]
+fn next_prerelease() {
+    let stable = Version::parse("1.3.0").unwrap();
+    let versions: Vec<_> = [
+        "1.2.0",
+        "1.3.0-beta.1",
+        "1.3.0-beta.2",
+        "1.3.0-rc.1",
+        "1.2.0-beta.7",
+    ]
+    .iter()
+    .map(|v| Version::parse(v).unwrap())
+    .collect();
+
+    assert_eq!(
+        get_next_prerelease(&stable, "beta", &versions).unwrap(),
+        Version::parse("1.3.0-beta.3").unwrap()
+    );
+    assert_eq!(
+        get_next_prerelease(&stable, "rc", &versions).unwrap(),
+        Version::parse("1.3.0-rc.2").unwrap()
+    );
+    assert_eq!(
+        get_next_prerelease(&stable, "alpha", &versions).unwrap(),
+        Version::parse("1.3.0-alpha.1").unwrap()
+    );
+
+    // a new stable target restarts the numbering
+    let stable = Version::parse("2.0.0").unwrap();
+    assert_eq!(
+        get_next_prerelease(&stable, "beta", &versions).unwrap(),
+        Version::parse("2.0.0-beta.1").unwrap()
+    );
+
+    // NB: `betas.1` is not a `beta` pre-release
+    let versions = vec![Version::parse("2.0.0-betas.4").unwrap()];
+    assert_eq!(
+        get_next_prerelease(&stable, "beta", &versions).unwrap(),
+        Version::parse("2.0.0-beta.1").unwrap()
+    );
+
+    assert!(get_next_prerelease(&stable, "be ta", &versions).is_err());
+}
+
+#[test]
+fn pre_stable_policies() {
+    let v = |s: &str| Version::parse(s).unwrap();
+    let curr = v("0.3.2");
+
+    for (policy, major, minor, patch) in [
+        (PreStablePolicy::Strict, "1.0.0", "0.4.0", "0.3.3"),
+        (PreStablePolicy::BreakingMinor, "0.4.0", "0.3.3", "0.3.3"),
+    ] {
+        assert_eq!(bump_version(&curr, Bump::Major, policy), v(major));
+        assert_eq!(bump_version(&curr, Bump::Minor, policy), v(minor));
+        assert_eq!(bump_version(&curr, Bump::Patch, policy), v(patch));
+    }
+
+    // NB: the policy only applies before 1.0.0
+    let curr = v("1.3.2-beta.1");
+    assert_eq!(
+        bump_version(&curr, Bump::Minor, PreStablePolicy::BreakingMinor),
+        v("1.4.0")
+    );
+}
@@ -0,0 +1,70 @@
+//! Testing of revert commits
+
+use gitext::{
+    config::Config, conventional::ConventionalCommitMessage, version::get_cancelled_commits,
+};
+use indoc::indoc;
+
+#[test]
+fn git_revert() {
+    let msg = indoc!(
+        r#"Revert "feat(api): add endpoint"
+
+        This reverts commit 8f1c2a7d9e0b3c4d5e6f708192a3b4c5d6e7f809."#
+    );
+
+    let commit = ConventionalCommitMessage::parse_with_config(msg, &Config::default()).unwrap();
+    assert!(commit.is_revert());
+    assert_eq!(commit.r#type, "revert");
+    assert_eq!(commit.scope, None);
+    assert_eq!(commit.subject, "feat(api): add endpoint");
+    assert_eq!(
+        commit.reverted_commit(),
+        Some("8f1c2a7d9e0b3c4d5e6f708192a3b4c5d6e7f809")
+    );
+}
+
+#[test]
+fn conventional_revert() {
+    let msg = indoc!(
+        "revert: feat(api): add endpoint
+
+        Refs: 8f1c2a7"
+    );
+
+    let commit = ConventionalCommitMessage::parse_with_config(msg, &Config::default()).unwrap();
+    assert!(commit.is_revert());
+    assert_eq!(commit.subject, "feat(api): add endpoint");
+    assert_eq!(commit.reverted_commit(), Some("8f1c2a7"));
+
+    // NB: only revert commits have a reverted commit
+    let msg = indoc!(
+        "fix: handle errors
+
+        Refs: 8f1c2a7"
+    );
+    let commit = ConventionalCommitMessage::parse_with_config(msg, &Config::default()).unwrap();
+    assert_eq!(commit.reverted_commit(), None);
+}
+
+#[test]
+fn cancelled_commits() {
+    // latest to earliest
+    let commits = vec![
+        ("d4", None),
+        ("c3", Some("a1")),
+        ("b2", Some("e0")),
+        ("a1", None),
+    ];
+    let cancelled = get_cancelled_commits(&commits);
+    assert_eq!(cancelled.len(), 2);
+    assert!(cancelled.contains("c3"));
+    assert!(cancelled.contains("a1"));
+
+    // reverted revert
+    let commits = vec![("c3", Some("b2")), ("b2", Some("a1")), ("a1", None)];
+    let cancelled = get_cancelled_commits(&commits);
+    assert_eq!(cancelled.len(), 2);
+    assert!(cancelled.contains("c3"));
+    assert!(cancelled.contains("b2"));
+}
@@ -0,0 +1,79 @@
+//! Testing of the serialization of parsed commits
+
+use gitext::{
+    config::Config,
+    conventional::ConventionalCommitMessage,
+    lint::{LintReport, Severity},
+};
+use indoc::indoc;
+use serde_json::json;
+
+#[test]
+fn serialize_commit() {
+    let msg = indoc!(
+        "feat(ui,api)!: add endpoint
+
+    commit body
+
+    Closes #12
+    Refs: PROJ-123
+    Signed-off-by: John Doe"
+    );
+
+    let commit = ConventionalCommitMessage::parse_with_config(msg, &Config::default()).unwrap();
+    let value = serde_json::to_value(&commit).unwrap();
+
+    assert_eq!(
+        value,
+        json!({
+            "type": "feat",
+            "scope": "ui,api",
+            "scopes": ["ui", "api"],
+            "subject": "add endpoint",
+            "body": "commit body",
+            "breaking_change": "",
+            "issues": [
+                { "action": "closes", "repo": null, "id": { "issue": 12 } },
+                { "action": "refs", "repo": null, "id": { "key": "PROJ-123" } }
+            ],
+            "trailers": [
+                { "token": "Signed-off-by", "separator": "colon", "value": "John Doe" }
+            ],
+            "footer": [
+                { "issues": [{ "action": "closes", "repo": null, "id": { "issue": 12 } }] },
+                { "issues": [{ "action": "refs", "repo": null, "id": { "key": "PROJ-123" } }] },
+                { "trailer": { "token": "Signed-off-by", "separator": "colon", "value": "John Doe" } }
+            ],
+            "gitmoji": null
+        })
+    );
+
+    // roundtrip (NB: the scopes, issues and trailers lists are ignored)
+    let deserialized: ConventionalCommitMessage = serde_json::from_value(value).unwrap();
+    assert_eq!(deserialized, commit);
+}
+
+#[test]
+fn deserialize_partial() {
+    let commit: ConventionalCommitMessage =
+        serde_json::from_str(r#"{ "type": "fix", "subject": "handle errors" }"#).unwrap();
+    assert_eq!(commit.to_string(), "fix: handle errors");
+}
+
+#[test]
+fn lint_report() {
+    let (commit, diagnostics) =
+        ConventionalCommitMessage::lint("fix: Handle errors", &Config::default());
+    let report = LintReport::new(commit, diagnostics);
+    assert!(!report.valid);
+    assert_eq!(report.diagnostics[0].severity, Severity::Error);
+
+    let value = serde_json::to_value(&report).unwrap();
+    assert_eq!(value["valid"], json!(false));
+    assert_eq!(value["commit"]["subject"], json!("Handle errors"));
+    assert_eq!(value["diagnostics"][0]["rule"], json!("subject-case"));
+    assert_eq!(
+        value["diagnostics"][0]["span"],
+        json!({ "line": 1, "column": 6, "length": 1 })
+    );
+}
@@ -0,0 +1,153 @@
+//! Testing of merge and autosquash commits
+
+use gitext::{
+    config::{CommitPoliciesConfig, CommitPolicy, Config},
+    conventional::{special::SpecialCommit, ConventionalCommitMessage},
+    git::GitCommit,
+    lint::Span,
+};
+use indoc::indoc;
+
+#[test]
+fn detect() {
+    let msg = indoc!(
+        "Merge pull request #12 from user/branch
+
+        feat(api): add endpoint"
+    );
+    assert_eq!(
+        SpecialCommit::detect(msg),
+        Some(SpecialCommit::Merge(Some("feat(api): add endpoint")))
+    );
+    assert_eq!(
+        SpecialCommit::detect("Merge branch 'main' into dev"),
+        Some(SpecialCommit::Merge(None))
+    );
+    assert_eq!(
+        SpecialCommit::detect("fixup! fixup! feat: add endpoint"),
+        Some(SpecialCommit::Fixup("feat: add endpoint"))
+    );
+    assert_eq!(
+        SpecialCommit::detect("squash! fix: handle errors"),
+        Some(SpecialCommit::Squash("fix: handle errors"))
+    );
+
+    let msg = indoc!(
+        "amend! feat: add endpoint
+
+        feat: add the users endpoint"
+    );
+    assert_eq!(
+        SpecialCommit::detect(msg),
+        Some(SpecialCommit::Amend("feat: add the users endpoint"))
+    );
+
+    assert_eq!(
+        SpecialCommit::detect("Merge remote-tracking branch 'origin/main' into dev"),
+        Some(SpecialCommit::Merge(None))
+    );
+    assert_eq!(
+        SpecialCommit::detect("Merge tag 'v1.2.0'"),
+        Some(SpecialCommit::Merge(None))
+    );
+
+    assert_eq!(SpecialCommit::detect("feat: add endpoint"), None);
+    // NB: only the headers created by git are merge commits
+    assert_eq!(SpecialCommit::detect("Merge whatever I want"), None);
+    let (_, diagnostics) =
+        ConventionalCommitMessage::lint("Merge whatever I want", &Config::default());
+    assert_eq!(diagnostics[0].rule, "header-format");
+
+    // commits with several parents
+    let commit = GitCommit {
+        parents: vec!["a".to_string(), "b".to_string()],
+        merge: true,
+        message: "Merge feature\n\nfeat: add endpoint".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        SpecialCommit::detect_commit(&commit),
+        Some(SpecialCommit::Merge(Some("feat: add endpoint")))
+    );
+    let commit = GitCommit {
+        message: "Merge whatever I want".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(SpecialCommit::detect_commit(&commit), None);
+}
+
+#[test]
+fn policies() {
+    let merge = indoc!(
+        "Merge pull request #12 from user/branch
+
+        feat(api): add endpoint"
+    );
+    let fixup = "fixup! fix: handle errors";
+
+    // default: skipped
+    let mut config = Config::default();
+    assert_eq!(
+        ConventionalCommitMessage::parse_log_message(merge, &config).unwrap(),
+        None
+    );
+    assert_eq!(
+        ConventionalCommitMessage::parse_log_message(fixup, &config).unwrap(),
+        None
+    );
+
+    config.commit.policies.merge = CommitPolicy::PrTitle;
+    config.commit.policies.fixup = CommitPolicy::Referenced;
+    let commit = ConventionalCommitMessage::parse_log_message(merge, &config)
+        .unwrap()
+        .unwrap();
+    assert_eq!(commit.r#type, "feat");
+    assert_eq!(commit.subject, "add endpoint");
+    let commit = ConventionalCommitMessage::parse_log_message(fixup, &config)
+        .unwrap()
+        .unwrap();
+    assert_eq!(commit.r#type, "fix");
+
+    // NB: a merge commit without body is skipped
+    assert_eq!(
+        ConventionalCommitMessage::parse_log_message("Merge branch 'dev'", &config).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn policies_validation() {
+    assert!(CommitPoliciesConfig::default().validate().is_ok());
+
+    let policies = CommitPoliciesConfig {
+        merge: CommitPolicy::Referenced,
+        ..Default::default()
+    };
+    assert!(policies.validate().is_err());
+    let policies = CommitPoliciesConfig {
+        amend: CommitPolicy::PrTitle,
+        ..Default::default()
+    };
+    assert!(policies.validate().is_err());
+}
+
+#[test]
+fn autosquash_diagnostic() {
+    let msg = "squash! feat: add endpoint";
+    let (commit, diagnostics) = ConventionalCommitMessage::lint(msg, &Config::default());
+    assert!(commit.is_none());
+    assert!(diagnostics.is_empty());
+
+    let diagnostic = SpecialCommit::detect(msg)
+        .unwrap()
+        .autosquash_diagnostic(msg)
+        .unwrap();
+    assert_eq!(diagnostic.rule, "autosquash");
+    assert_eq!(diagnostic.span, Span::new(1, 1, 7));
+
+    let msg = "Merge branch 'dev'";
+    assert!(SpecialCommit::detect(msg)
+        .unwrap()
+        .autosquash_diagnostic(msg)
+        .is_none());
+}
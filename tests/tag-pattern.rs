@@ -0,0 +1,91 @@
+//! Testing of the tag patterns
+
+use chrono::Utc;
+use gitext::{
+    config::{Config, PreStablePolicy},
+    git::GitTag,
+    version::{
+        scheme::SemVer,
+        tag::{tag_pattern, TagPattern},
+        IntoSemverGitTags,
+    },
+};
+use semver::Version;
+
+fn git_tag(tag: &str) -> GitTag {
+    GitTag {
+        tag: tag.to_string(),
+        hash: "".to_string(),
+        commit_hash: "".to_string(),
+        date: Utc::now(),
+        message: None,
+    }
+}
+
+#[test]
+fn parse_format() {
+    let pattern = TagPattern::new("v{version}", None).unwrap();
+    assert_eq!(pattern.parse("v1.2.3"), Some("1.2.3"));
+    assert_eq!(pattern.parse("1.2.3"), None);
+    assert_eq!(pattern.format("1.2.3"), "v1.2.3");
+
+    let pattern = TagPattern::new("release-{version}", None).unwrap();
+    assert_eq!(pattern.parse("release-1.2.3-beta.1"), Some("1.2.3-beta.1"));
+    assert_eq!(pattern.parse("v1.2.3"), None);
+    assert_eq!(pattern.format("1.2.3"), "release-1.2.3");
+
+    let pattern = TagPattern::new("{package}@{version}", Some("core")).unwrap();
+    assert_eq!(pattern.parse("core@1.2.3"), Some("1.2.3"));
+    assert_eq!(pattern.parse("cli@1.2.3"), None);
+    assert_eq!(pattern.format("1.2.3"), "core@1.2.3");
+
+    // NB: special regex characters are escaped
+    let pattern = TagPattern::new("v.{version}", None).unwrap();
+    assert_eq!(pattern.parse("v.1.2.3"), Some("1.2.3"));
+    assert_eq!(pattern.parse("vx1.2.3"), None);
+}
+
+#[test]
+fn default_pattern() {
+    // NB: the tags without the `v` prefix are versions
+    let pattern = tag_pattern(&Config::default()).unwrap();
+    assert_eq!(pattern.parse("v1.2.3"), Some("1.2.3"));
+    assert_eq!(pattern.parse("1.2.3"), Some("1.2.3"));
+    assert_eq!(pattern.format("1.2.3"), "v1.2.3");
+
+    let scheme = SemVer::new(PreStablePolicy::default());
+    let tags = vec![git_tag("1.0.0"), git_tag("deploy-prod"), git_tag("v1.1.0")];
+    let versions: Vec<_> = tags
+        .into_versions(&pattern, &scheme)
+        .into_iter()
+        .map(|t| t.version)
+        .collect();
+    assert_eq!(versions, vec![Version::new(1, 0, 0), Version::new(1, 1, 0)]);
+}
+
+#[test]
+fn invalid_patterns() {
+    assert!(TagPattern::new("release", None).is_err());
+    assert!(TagPattern::new("{version}-{version}", None).is_err());
+    assert!(TagPattern::new("{package}@{version}", None).is_err());
+}
+
+#[test]
+fn non_version_tags_are_ignored() {
+    let pattern = TagPattern::new("v{version}", None).unwrap();
+    let scheme = SemVer::new(PreStablePolicy::default());
+
+    let tags = vec![
+        git_tag("deploy-prod"),
+        git_tag("v1.0.0"),
+        git_tag("legacy"),
+        git_tag("vnext"),
+        git_tag("v1.1.0"),
+    ];
+    let versions: Vec<_> = tags
+        .into_versions(&pattern, &scheme)
+        .into_iter()
+        .map(|t| t.version)
+        .collect();
+    assert_eq!(versions, vec![Version::new(1, 0, 0), Version::new(1, 1, 0)]);
+}
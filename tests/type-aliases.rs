@@ -0,0 +1,100 @@
+//! Testing of commit type aliases and suggestions
+
+use gitext::{config::Config, conventional::ConventionalCommitMessage, utils::StringExt};
+
+#[test]
+fn aliases() {
+    let config = Config::default();
+
+    for (msg, r#type) in [
+        ("feature: add endpoint", "feat"),
+        ("bugfix(api): handle errors", "fix"),
+        ("doc: update readme", "docs"),
+        ("feat: add endpoint", "feat"),
+    ] {
+        let commit = ConventionalCommitMessage::parse_with_config(msg, &config).unwrap();
+        assert_eq!(commit.r#type, r#type);
+    }
+
+    // NB: an uppercase alias is still a type-case violation
+    let (commit, diagnostics) = ConventionalCommitMessage::lint("Feature: add endpoint", &config);
+    assert_eq!(commit.unwrap().r#type, "feat");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, "type-case");
+
+    // custom aliases
+    let mut config = Config::default();
+    config
+        .commit
+        .aliases
+        .insert("hotfix".to_string(), "fix".to_string());
+    let commit = ConventionalCommitMessage::parse_with_config("hotfix: patch", &config).unwrap();
+    assert_eq!(commit.r#type, "fix");
+    assert_eq!(commit.to_string(), "fix: patch");
+    assert_eq!(config.canonical_commit_type("hotfix"), "fix");
+    assert_eq!(config.canonical_commit_type("feat"), "feat");
+
+    // aliases are not applied without config
+    assert!(ConventionalCommitMessage::parse(
+        "feature: add endpoint",
+        &Config::default().valid_commit_types()
+    )
+    .is_err());
+}
+
+#[test]
+fn aliases_valid_types() {
+    // NB: a valid type is not aliased
+    let mut config = Config::default();
+    config
+        .commit
+        .types
+        .insert("feature".to_string(), "Features".to_string());
+    let commit =
+        ConventionalCommitMessage::parse_with_config("feature: add endpoint", &config).unwrap();
+    assert_eq!(commit.r#type, "feature");
+    assert_eq!(config.canonical_commit_type("feature"), "feature");
+
+    // the alias must refer to a valid type
+    let mut config = Config::default();
+    config
+        .commit
+        .aliases
+        .insert("hotfix".to_string(), "urgent".to_string());
+    let err = ConventionalCommitMessage::parse_with_config("hotfix: patch", &config)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("invalid type 'urgent'"), "{err}");
+    let (_, diagnostics) = ConventionalCommitMessage::lint("Hotfix: patch", &config);
+    let rules: Vec<_> = diagnostics.iter().map(|d| d.rule.as_str()).collect();
+    assert_eq!(rules, vec!["type-case", "type-enum"]);
+}
+
+#[test]
+fn type_suggestion() {
+    let config = Config::default();
+
+    let err = ConventionalCommitMessage::parse_with_config("feta: add endpoint", &config)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("did you mean 'feat'?"), "{err}");
+
+    let err = ConventionalCommitMessage::parse_with_config("Chroe: bump deps", &config)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("did you mean 'chore'?"), "{err}");
+
+    let err = ConventionalCommitMessage::parse_with_config("wip: stuff", &config)
+        .unwrap_err()
+        .to_string();
+    assert!(!err.contains("did you mean"), "{err}");
+}
+
+#[test]
+fn edit_distance() {
+    assert_eq!("feat".edit_distance("feat"), 0);
+    assert_eq!("feta".edit_distance("feat"), 2);
+    assert_eq!("fix".edit_distance("fixes"), 2);
+    assert_eq!("".edit_distance("ci"), 2);
+    assert_eq!("kitten".edit_distance("sitting"), 3);
+}